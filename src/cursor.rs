@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::window;
 use std::time::Instant;
+use crate::{input, settings, AppState};
 
 #[derive(Resource)]
 struct LastCursorMovement(Instant);
@@ -17,16 +18,21 @@ impl Plugin for AutoHideCursorPlugin {
     }
 }
 
+// The cursor is never hidden while it is used for steering
 fn auto_hide_cursor(
     mut window_query: Query<&mut Window>,
     mut cursor_moved: EventReader<window::CursorMoved>,
     mut last_cursor_movement: ResMut<LastCursorMovement>,
+    settings: Res<settings::Settings>,
+    app_state: Res<State<AppState>>,
 ) {
     for _ in cursor_moved.read() {
         last_cursor_movement.0 = Instant::now();
     }
 
+    let steering = settings.control_scheme == input::ControlScheme::Pointer && app_state.get() == &AppState::Game;
+
     if let Ok(mut window) = window_query.get_single_mut() {
-        window.cursor.visible = last_cursor_movement.0.elapsed().as_secs_f32() < HIDE_SECONDS;
+        window.cursor.visible = steering || last_cursor_movement.0.elapsed().as_secs_f32() < HIDE_SECONDS;
     }
 }
\ No newline at end of file
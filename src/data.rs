@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy::asset::io::file::FileAssetReader;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USER_DIRECTORY_NAME: &str = "paper_plane";
+
+// Reads a ron data file from the assets folder
+// Data files are read directly instead of through the asset server, so they can be used before any systems have run
+pub fn load_ron<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let full_path = FileAssetReader::get_base_path().join("assets").join(path);
+
+    let contents = fs::read_to_string(&full_path).map_err(|error| format!("Couldn't read {}: {}", full_path.display(), error))?;
+    ron::from_str(&contents).map_err(|error| format!("Couldn't parse {}: {}", full_path.display(), error))
+}
+
+// Path of a file inside the games folder in one of the users directories, such as dirs::data_dir
+pub fn user_file_path(directory: Option<PathBuf>, file_name: &str) -> Option<PathBuf> {
+    directory.map(|directory| directory.join(USER_DIRECTORY_NAME).join(file_name))
+}
+
+// Reads a ron file written by write_user_ron
+// Returns None when the file doesn't exist yet or can't be parsed
+pub fn read_user_ron<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Couldn't parse {}: {}", path.display(), error);
+            None
+        },
+    }
+}
+
+// Writes a value to a ron file, creating its directory if needed
+pub fn write_user_ron<T: Serialize>(path: &Path, value: &T) {
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| {
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+            }
+            fs::write(path, contents).map_err(|error| error.to_string())
+        });
+
+    if let Err(error) = result {
+        warn!("Couldn't save {}: {}", path.display(), error);
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use crate::{generic, game, AppState};
+use game::{platforms, difficulty, sprite_scaler};
+
+// The adaptive offset scales the players speed, and always stays within this range
+pub const OFFSET_RANGE: generic::Range<f32> = generic::Range {min: -0.3, max: 0.3};
+
+const CLOSE_CALLS_TO_EASE: u32 = 3; // Near misses in a row before the difficulty eases off
+const CLEAN_PASSES_TO_PUSH: u32 = 10; // Platform rows passed without a near miss before the difficulty is pushed harder
+const EARLY_DEATHS_TO_EASE: u32 = 2; // Early deaths in a row before the difficulty eases off
+const EARLY_DEATH_SCORE: i32 = 10; // Dying with a score lower than this is an early death
+
+const EASE_STEP: f32 = 0.05;
+const PUSH_STEP: f32 = 0.03;
+
+// Tracks how the player is doing, and how much to adjust the difficulty by
+// Kept between runs, so repeated early deaths can be noticed
+#[derive(Resource, Default)]
+pub struct AdaptiveDifficulty {
+    pub enabled: bool,
+    pub offset: f32, // Negative when the game is easier than normal, positive when it is harder
+
+    close_calls: u32,
+    clean_passes: u32,
+    early_deaths: u32,
+}
+
+pub struct AdaptivePlugin;
+
+impl Plugin for AdaptivePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(AdaptiveDifficulty::default())
+            .add_systems(OnEnter(AppState::Game), apply_adaptive_offset)
+            .add_systems(OnEnter(game::GameState::GameOver), adapt_to_death.run_if(adaptive_enabled))
+            .add_systems(Update, adapt_to_platforms.after(platforms::pass_platforms).after(game::recalculate_difficulty).run_if(adaptive_enabled).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
+    }
+}
+
+impl AdaptiveDifficulty {
+    fn adjust(&mut self, amount: f32) {
+        self.offset = (self.offset + amount).clamp(OFFSET_RANGE.min, OFFSET_RANGE.max);
+    }
+
+    // Offset used by the game difficulty, which is 0 unless adaptive difficulty is enabled
+    pub fn active_offset(&self) -> f32 {
+        if self.enabled {
+            self.offset
+        } else {
+            0.0
+        }
+    }
+}
+
+fn adaptive_enabled(adaptive: Res<AdaptiveDifficulty>) -> bool {
+    adaptive.enabled
+}
+
+// Ease off after several close calls, and push harder when the player is passing platforms comfortably
+fn adapt_to_platforms(
+    mut platform_passed: EventReader<platforms::PlatformPassed>,
+    mut adaptive: ResMut<AdaptiveDifficulty>,
+    mut game: ResMut<game::Game>,
+    difficulty_curves: Res<difficulty::DifficultyCurves>,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+) {
+    let mut changed = false;
+
+    for passed in platform_passed.read() {
+        if passed.is_near_miss(scale_factor.current) {
+            adaptive.close_calls += 1;
+            adaptive.clean_passes = 0;
+
+            if adaptive.close_calls >= CLOSE_CALLS_TO_EASE {
+                adaptive.adjust(-EASE_STEP);
+                adaptive.close_calls = 0;
+                changed = true;
+            }
+        } else {
+            adaptive.clean_passes += 1;
+
+            if adaptive.clean_passes >= CLEAN_PASSES_TO_PUSH {
+                adaptive.adjust(PUSH_STEP);
+                adaptive.clean_passes = 0;
+                adaptive.close_calls = 0;
+                changed = true;
+            }
+        }
+    }
+
+    if changed {
+        let score = game.score;
+        game.difficulty.adaptive_offset = adaptive.active_offset();
+        game.difficulty.apply_curves(&difficulty_curves, score);
+    }
+}
+
+// Ease off when the player keeps dying early
+fn adapt_to_death(mut adaptive: ResMut<AdaptiveDifficulty>, game: Res<game::Game>) {
+    adaptive.close_calls = 0;
+    adaptive.clean_passes = 0;
+
+    if game.score < EARLY_DEATH_SCORE {
+        adaptive.early_deaths += 1;
+
+        if adaptive.early_deaths >= EARLY_DEATHS_TO_EASE {
+            adaptive.adjust(-EASE_STEP);
+            adaptive.early_deaths = 0; // Ease again after another EARLY_DEATHS_TO_EASE early deaths, not every one after this
+        }
+    } else {
+        adaptive.early_deaths = 0;
+    }
+}
+
+// Carry the adaptive offset into a new game
+fn apply_adaptive_offset(
+    adaptive: Res<AdaptiveDifficulty>,
+    mut game: ResMut<game::Game>,
+    difficulty_curves: Res<difficulty::DifficultyCurves>,
+) {
+    let score = game.score;
+    game.difficulty.adaptive_offset = adaptive.active_offset();
+    game.difficulty.apply_curves(&difficulty_curves, score);
+    game.difficulty.snap_to_targets();
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use crate::{art, generic, AppState, game};
-use game::sprite_scaler;
+use game::{sprite_scaler, corridor, tiles, theme};
 
 pub const LEVEL_TILE_WIDTH: usize = 4; // How many tiles wide to make the level
 
@@ -32,12 +33,18 @@ impl Plugin for BackgroundPlugin {
 }
 
 // Spawn a row of background walls when needed, and despawn unnecasary rows
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_background_walls(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tile_art: Res<tiles::TileArt>,
+    themes: Res<theme::Themes>,
+    mut active_theme: ResMut<theme::ActiveTheme>,
     screen_information: Res<generic::ScreenInformation>,
     bg_walls_query: Query<&BackgroundWall>,
     mut bg_walls_rows: ResMut<BackgroundWallRows>,
+    corridor: Res<corridor::Corridor>,
     scale_factor: Res<sprite_scaler::ScaleFactor>,
 ) {
     let wall_world_size = Vec2::new(
@@ -74,38 +81,40 @@ pub fn spawn_background_walls(
     }
 
     // Spawn rows
+    // Each row is a single mesh made from the wall tiles
     for row in 0..spawn_rows {
         let spawn_y_coordinate = init_spawn_y_coordinate - (wall_world_size.y * row as f32);
 
-        for i in 1..LEVEL_TILE_WIDTH + 1 {
-            let x_pos = (i as f32 * wall_world_size.x) - (wall_world_size.x / 2.0) + screen_information.x_deadspace;
-            commands.spawn(
-                (
-                    game::GameComponent,
-                    BackgroundWall {
-                        row:  bg_walls_rows.rows,
-                        y_coordinate: spawn_y_coordinate
-                    },
-                    SpriteBundle {
-                        transform: Transform {
-                            translation: Vec3::new(x_pos as f32, spawn_y_coordinate, 0.0),
-                            scale: Vec3::splat(scale_factor.current),
-                            ..default()
-                        },
-                        texture: asset_server.load(art::WALL_SPRITE_PATH),
-                        ..default()
-                    }
-                )
-            );
+        // Only add the tiles which are inside the corridor for this row
+        let mut row_mesh = tiles::TileMeshBuilder::default();
+        for i in corridor::row_tiles(corridor.insets_at_row(bg_walls_rows.rows)) {
+            let x_pos = (i as f32 * wall_world_size.x) + (wall_world_size.x / 2.0) + screen_information.x_deadspace;
+            row_mesh.push_tile(Vec2::new(x_pos, 0.0), wall_world_size, 0.0, tiles::FULL_UV);
         }
 
+        commands.spawn(
+            (
+                game::GameComponent,
+                BackgroundWall {
+                    row:  bg_walls_rows.rows,
+                    y_coordinate: spawn_y_coordinate
+                },
+                MaterialMesh2dBundle {
+                    mesh: meshes.add(row_mesh.build()).into(),
+                    material: active_theme.next_wall_material(&themes, &tile_art, &mut materials),
+                    transform: Transform::from_xyz(0.0, spawn_y_coordinate, 0.0),
+                    ..default()
+                },
+            )
+        );
+
         bg_walls_rows.rows += 1;
     }
 }
 
 // Scroll walls to make the illusion of the player moving downwards
 // The scroll speed is the players y speed
-fn despawn_walls(
+pub fn despawn_walls(
     mut commands: Commands,
     mut bg_walls_query: Query<(Entity, &BackgroundWall)>,
     screen_information: Res<generic::ScreenInformation>,
@@ -0,0 +1,81 @@
+// Headless benchmark for spawning platforms and background walls
+// Run with `cargo test --release bench -- --ignored --nocapture`
+use bevy::prelude::*;
+use std::time::Instant;
+use crate::{art, generic, game, settings};
+use game::{sprite_scaler, platforms, background, tiles, theme, difficulty};
+
+const BENCH_FRAMES: u32 = 10_000;
+const SCROLL_PER_FRAME: f32 = 20.0; // World units the visible area moves down every frame
+
+// Screen the benchmark pretends to run on
+const WINDOW_SIZE: Vec2 = Vec2::new(1920.0, 1080.0);
+const SCALE_FACTOR: f32 = 3.0;
+
+// Moves the visible area downwards, like the camera following the player
+fn scroll_visible_area(mut screen_information: ResMut<generic::ScreenInformation>) {
+    screen_information.y_visible_area.min -= SCROLL_PER_FRAME;
+    screen_information.y_visible_area.max -= SCROLL_PER_FRAME;
+}
+
+fn bench_app() -> App {
+    let wall_world_width = art::WALL_SPRITE_SIZE.x * SCALE_FACTOR;
+
+    let mut app = App::new();
+    app
+        .add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .add_event::<game::ScoreIncrease>()
+
+        .insert_resource(sprite_scaler::ScaleFactor {current: SCALE_FACTOR})
+        .insert_resource(generic::ScreenInformation {
+            x_deadspace: (WINDOW_SIZE.x - background::LEVEL_TILE_WIDTH as f32 * wall_world_width) / 2.0,
+            y_visible_area: generic::Range {min: 0.0, max: WINDOW_SIZE.y},
+            window_width: WINDOW_SIZE.x,
+            window_height: WINDOW_SIZE.y,
+        })
+
+        .insert_resource(theme::Themes::load())
+        .insert_resource(theme::ActiveTheme::default())
+        .insert_resource(difficulty::DifficultyPreset::default())
+        .insert_resource(settings::Settings::default())
+
+        .add_systems(Startup, (game::insert_game_resouorces, tiles::load_tile_art))
+        .add_systems(Update, (
+            scroll_visible_area,
+            platforms::spawn_platforms,
+            platforms::despawn_platforms,
+            background::spawn_background_walls,
+            background::despawn_walls,
+        ).chain());
+
+    app
+}
+
+#[test]
+#[ignore]
+fn bench_tile_spawning() {
+    let mut app = bench_app();
+    app.update(); // Run startup systems before timing
+
+    let mut peak_entities = 0;
+    let start = Instant::now();
+    for _ in 0..BENCH_FRAMES {
+        app.update();
+        peak_entities = peak_entities.max(app.world.entities().len());
+    }
+    let elapsed = start.elapsed().as_secs_f32();
+
+    let platforms_spawned = app.world.resource::<platforms::Platforms>().total_platforms;
+    let wall_rows_spawned = app.world.resource::<background::BackgroundWallRows>().rows;
+
+    println!("frames: {}, time: {:.3}s ({:.0} frames per second)", BENCH_FRAMES, elapsed, BENCH_FRAMES as f32 / elapsed);
+    println!("platforms spawned: {} ({:.0} per second)", platforms_spawned, platforms_spawned as f32 / elapsed);
+    println!("wall rows spawned: {} ({:.0} per second)", wall_rows_spawned, wall_rows_spawned as f32 / elapsed);
+    println!("live entities: {} at the end, {} at peak", app.world.entities().len(), peak_entities);
+
+    assert!(platforms_spawned > 0);
+    assert!(wall_rows_spawned > 0);
+}
@@ -3,7 +3,7 @@ use bevy::window::{PrimaryWindow, WindowResized};
 use crate::{art, game, AppState};
 
 #[derive(Component)]
-struct CameraBackground;
+pub struct CameraBackground;
 
 pub struct CameraPlugin;
 
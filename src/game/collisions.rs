@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use crate::{generic, game, AppState};
-use game::sprite_scaler;
+use crate::{art, generic, game, AppState};
+use game::{sprite_scaler, corridor};
 
 pub const REMOVE_PLATFORM_X_PIXELS: f32 = 2.0; // How many pixels to remove from the edge of the platform hitboxes
 
@@ -15,49 +15,68 @@ impl Plugin for CollisionPlugin {
     }
 }
 
-#[derive(Event)]
-pub struct Collision;
+// What the player crashed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obstacle {
+    Wall,
+    Platform,
+}
+
+// Sent when the player crashes, ending the run
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct Collision {
+    pub obstacle: Obstacle,
+    pub side: generic::Direction, // Side of the level the player crashed on
+}
 
 // Send a collision event when a collision is detected between the player and an obstacle
-fn detect_collisions(
+pub fn detect_collisions(
     platforms: Res<game::platforms::Platforms>,
     player_query: Query<&Transform, With<game::player::Player>>,
     screen_information: Res<generic::ScreenInformation>,
+    corridor: Res<corridor::Corridor>,
     mut next_state: ResMut<NextState<game::GameState>>,
+    mut collision_events: EventWriter<Collision>,
     scale_factor: Res<sprite_scaler::ScaleFactor>,
 ) {
     if let Ok(player_transform) = player_query.get_single() {
         let player_translation = player_transform.translation;
 
-        let mut collision = false;
+        let mut collision = None;
 
         // Detect if player is colliding with the wall
-        if player_translation.x > screen_information.window_width - screen_information.x_deadspace {
-            collision = true;
-        } else if player_translation.x < screen_information.x_deadspace {
-            collision = true;
+        // The walls step in and out as the level gets narrower and wider
+        let wall_world_size = art::WALL_SPRITE_SIZE * scale_factor.current;
+        let level_bounds = corridor.bounds_at(player_translation.y, &screen_information, wall_world_size);
+        if player_translation.x > level_bounds.max || player_translation.x < level_bounds.min {
+            collision = Some(Obstacle::Wall);
         }
 
         //println!("wall collision: {}, {}, {}, {}", collision, screen_information.window_width - screen_information.x_deadspace, screen_information.x_deadspace, player_translation.x);
 
         // Detect if player is colliding with any existing platforms
         for platform in platforms.platforms_vec.iter() {
-            let x_collision = match platform.side {
-                generic::Direction::Left => player_translation.x < platform.hitbox[0].max - REMOVE_PLATFORM_X_PIXELS * scale_factor.current,
-                generic::Direction::Right => player_translation.x > platform.hitbox[0].max + REMOVE_PLATFORM_X_PIXELS * scale_factor.current,
-            };
+            let x_collision = player_translation.x > platform.hitbox[0].min + REMOVE_PLATFORM_X_PIXELS * scale_factor.current
+                && player_translation.x < platform.hitbox[0].max - REMOVE_PLATFORM_X_PIXELS * scale_factor.current;
 
             let y_collision = player_translation.y < platform.hitbox[1].max && player_translation.y > platform.hitbox[1].min;
 
             // Only update the collision if the player hasn't allready collided with the wall
-            if !collision {
-                collision = x_collision & y_collision;
+            if collision.is_none() && x_collision && y_collision {
+                collision = Some(Obstacle::Platform);
             }
             
         }
 
         // When there is a collision change app to game over state
-        if collision {
+        if let Some(obstacle) = collision {
+            let side = if player_translation.x < (level_bounds.min + level_bounds.max) / 2.0 {
+                generic::Direction::Left
+            } else {
+                generic::Direction::Right
+            };
+
+            collision_events.send(Collision {obstacle, side});
             next_state.set(game::GameState::GameOver);
         }
     }    
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::{generic, game};
+use game::background::LEVEL_TILE_WIDTH;
+
+const SECTION_ROWS: u32 = 8; // How many wall rows each corridor section lasts for
+const START_SECTIONS: u32 = 2; // Sections at the start of the game which are always full width
+
+// Wall tiles the walls step inwards by on the [left, right] side during a narrow section
+const NARROW_INSETS: [[u32; 2]; 3] = [[1, 0], [0, 1], [1, 1]];
+
+// Describes how wide the level is at every depth
+// Narrow sections alternate with full width sections, so the corridor narrows and then widens again
+// Each section is generated from the seed, so any system can look up the corridor width at any depth
+#[derive(Resource)]
+pub struct Corridor {
+    pub seed: u64,
+}
+
+impl Corridor {
+    // Wall tiles removed from the [left, right] side of the level for a wall row
+    pub fn insets_at_row(&self, row: u32) -> [u32; 2] {
+        let section = row / SECTION_ROWS;
+
+        // Every other section is narrow, the rest are full width
+        let narrow = section >= START_SECTIONS && section % 2 == 1;
+        if !narrow {
+            return [0, 0];
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed ^ section as u64);
+        NARROW_INSETS[rng.gen_range(0..NARROW_INSETS.len())]
+    }
+
+    // Minimum and maximum x coordinate of the level at a y coordinate
+    pub fn bounds_at(&self, y: f32, screen_information: &generic::ScreenInformation, wall_world_size: Vec2) -> generic::Range<f32> {
+        let insets = self.insets_at_row(wall_row_at(y, screen_information, wall_world_size));
+        inset_bounds(insets, screen_information, wall_world_size)
+    }
+
+    // Narrowest and widest level bounds found between two y coordinates
+    // Returns (narrowest, widest)
+    pub fn bounds_between(
+        &self,
+        y_range: generic::Range<f32>,
+        screen_information: &generic::ScreenInformation,
+        wall_world_size: Vec2,
+    ) -> (generic::Range<f32>, generic::Range<f32>) {
+        let mut narrowest = self.bounds_at(y_range.max, screen_information, wall_world_size);
+        let mut widest = narrowest;
+
+        let top_row = wall_row_at(y_range.max, screen_information, wall_world_size);
+        let bottom_row = wall_row_at(y_range.min, screen_information, wall_world_size);
+        for row in top_row + 1..bottom_row + 1 {
+            let bounds = inset_bounds(self.insets_at_row(row), screen_information, wall_world_size);
+
+            narrowest.min = narrowest.min.max(bounds.min);
+            narrowest.max = narrowest.max.min(bounds.max);
+            widest.min = widest.min.min(bounds.min);
+            widest.max = widest.max.max(bounds.max);
+        }
+
+        (narrowest, widest)
+    }
+}
+
+// Indices of the wall tiles which make up a row with the given insets
+pub fn row_tiles(insets: [u32; 2]) -> std::ops::Range<usize> {
+    insets[0] as usize..LEVEL_TILE_WIDTH - insets[1] as usize
+}
+
+// Level bounds when the walls are stepped inwards by insets
+fn inset_bounds(insets: [u32; 2], screen_information: &generic::ScreenInformation, wall_world_size: Vec2) -> generic::Range<f32> {
+    generic::Range {
+        min: screen_information.x_deadspace + insets[0] as f32 * wall_world_size.x,
+        max: screen_information.window_width - screen_information.x_deadspace - insets[1] as f32 * wall_world_size.x,
+    }
+}
+
+// Index of the wall row at a y coordinate
+// The first wall row is spawned with its top at the top of the screen, every following row is spawned below the last
+fn wall_row_at(y: f32, screen_information: &generic::ScreenInformation, wall_world_size: Vec2) -> u32 {
+    let row = ((screen_information.window_height - y) / wall_world_size.y).floor();
+
+    if row < 0.0 {
+        0
+    } else {
+        row as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_sections_full_width() {
+        let corridor = Corridor {seed: 7253};
+
+        for row in 0..START_SECTIONS * SECTION_ROWS {
+            assert_eq!(corridor.insets_at_row(row), [0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_narrow_sections_widen_again() {
+        let corridor = Corridor {seed: 7253};
+
+        let narrow_row = (START_SECTIONS + 1) * SECTION_ROWS;
+        assert_ne!(corridor.insets_at_row(narrow_row), [0, 0]);
+        assert_eq!(corridor.insets_at_row(narrow_row + SECTION_ROWS), [0, 0]);
+    }
+}
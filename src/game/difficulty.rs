@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{data, input};
+
+const DIFFICULTY_PATH: &str = "data/difficulty.ron";
+
+// Difficulty picked on the main menu before a run
+// Changes the difficulty at the start of the game, which the difficulty curves then build on
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+// Multipliers a preset applies to the start of game difficulty
+pub struct PresetMultipliers {
+    pub platform_gap: f32,
+    pub player_speed: f32,
+    pub steering_rate: f32,
+}
+
+impl PresetMultipliers {
+    // Both sets of multipliers applied one after the other
+    pub fn combine(&self, other: &PresetMultipliers) -> Self {
+        PresetMultipliers {
+            platform_gap: self.platform_gap * other.platform_gap,
+            player_speed: self.player_speed * other.player_speed,
+            steering_rate: self.steering_rate * other.steering_rate,
+        }
+    }
+}
+
+// Handicap for control schemes which are harder to play with, so they stay fair
+pub fn control_scheme_multipliers(control_scheme: input::ControlScheme) -> PresetMultipliers {
+    match control_scheme {
+        input::ControlScheme::Buttons | input::ControlScheme::Pointer => PresetMultipliers {platform_gap: 1.0, player_speed: 1.0, steering_rate: 1.0},
+        input::ControlScheme::OneButton => PresetMultipliers {platform_gap: 1.15, player_speed: 0.9, steering_rate: 1.0}, // Turning the other way takes longer with one button
+    }
+}
+
+// True when a control scheme plays with easier multipliers than the normal ones
+// Scores played with it are kept separate from other scores
+pub fn assisted(control_scheme: input::ControlScheme) -> bool {
+    let multipliers = control_scheme_multipliers(control_scheme);
+    multipliers.platform_gap > 1.0 || multipliers.player_speed < 1.0 || multipliers.steering_rate < 1.0
+}
+
+impl DifficultyPreset {
+    pub fn multipliers(&self) -> PresetMultipliers {
+        match self {
+            DifficultyPreset::Easy => PresetMultipliers {platform_gap: 1.2, player_speed: 0.85, steering_rate: 0.85},
+            DifficultyPreset::Normal => PresetMultipliers {platform_gap: 1.0, player_speed: 1.0, steering_rate: 1.0},
+            DifficultyPreset::Hard => PresetMultipliers {platform_gap: 0.9, player_speed: 1.15, steering_rate: 1.15},
+        }
+    }
+
+    // The preset after this one, wrapping back around to the first
+    pub fn next(&self) -> Self {
+        match self {
+            DifficultyPreset::Easy => DifficultyPreset::Normal,
+            DifficultyPreset::Normal => DifficultyPreset::Hard,
+            DifficultyPreset::Hard => DifficultyPreset::Easy,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Normal => "Normal",
+            DifficultyPreset::Hard => "Hard",
+        }
+    }
+}
+
+// What a curve's keyframes are placed over
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CurveAxis {
+    Level,
+    Score,
+}
+
+// Piecewise linear curve
+#[derive(Deserialize, Clone, Debug)]
+pub struct Curve {
+    pub over: CurveAxis,
+    pub keyframes: Vec<(f32, f32)>, // (level or score, value), ordered by level or score
+}
+
+// How the difficulty changes as the game goes on, loaded from DIFFICULTY_PATH
+#[derive(Resource, Deserialize, Clone, Debug)]
+pub struct DifficultyCurves {
+    pub points_per_level: i32, // The level number increments every points_per_level points
+
+    pub platform_gap: Curve, // Multiplier for the platform gap at the start of the game
+    pub platform_height: Curve, // Platform height in tiles
+    pub player_speed: Curve, // Multiplier for the player speed at the start of the game
+    pub platform_length_variance: Curve, // Player widths the length of a platform can vary by
+    pub double_sided_chance: Curve, // % Chance for a double sided platform to spawn
+    pub floating_chance: Curve, // % Chance for a floating platform to spawn
+}
+
+impl Curve {
+    fn constant(value: f32) -> Self {
+        Curve {over: CurveAxis::Level, keyframes: vec![(1.0, value)]}
+    }
+
+    // Value of the curve at a level and score
+    pub fn sample(&self, level: i32, score: i32) -> f32 {
+        let x = match self.over {
+            CurveAxis::Level => level as f32,
+            CurveAxis::Score => score as f32,
+        };
+
+        sample_keyframes(&self.keyframes, x)
+    }
+}
+
+impl DifficultyCurves {
+    // Load difficulty curves from DIFFICULTY_PATH, or fall back to the default curves when they can't be loaded
+    pub fn load() -> Self {
+        match data::load_ron::<DifficultyCurves>(DIFFICULTY_PATH) {
+            Ok(curves) => curves,
+            Err(error) => {
+                warn!("{}, using the default difficulty", error);
+                DifficultyCurves::default_curves()
+            },
+        }
+    }
+
+    // How far the score is through the current level, from 0 to 1
+    pub fn level_progress(&self, score: i32) -> f32 {
+        let points_per_level = self.points_per_level.max(1);
+        score.rem_euclid(points_per_level) as f32 / points_per_level as f32
+    }
+
+    pub fn default_curves() -> Self {
+        DifficultyCurves {
+            points_per_level: 20,
+
+            platform_gap: Curve {over: CurveAxis::Level, keyframes: vec![(1.0, 1.05), (101.0, 6.05)]},
+            platform_height: Curve {over: CurveAxis::Level, keyframes: vec![(1.0, 2.0), (7.0, 8.0)]},
+            player_speed: Curve {over: CurveAxis::Level, keyframes: vec![(1.0, 1.07), (101.0, 8.07)]},
+            platform_length_variance: Curve::constant(1.0),
+            double_sided_chance: Curve::constant(10.0),
+            floating_chance: Curve::constant(15.0),
+        }
+    }
+}
+
+// Linearly interpolate between the keyframes either side of x
+// Values before the first keyframe and after the last keyframe are held
+fn sample_keyframes(keyframes: &[(f32, f32)], x: f32) -> f32 {
+    if let (Some(first), Some(last)) = (keyframes.first(), keyframes.last()) {
+        if x <= first.0 {
+            return first.1;
+        }
+        if x >= last.0 {
+            return last.1;
+        }
+    } else {
+        return 0.0; // A curve without keyframes
+    }
+
+    for window in keyframes.windows(2) {
+        let (start, end) = (window[0], window[1]);
+
+        if x >= start.0 && x <= end.0 {
+            if end.0 == start.0 {
+                return end.1;
+            }
+
+            let progress = (x - start.0) / (end.0 - start.0);
+            return start.1 + (end.1 - start.1) * progress;
+        }
+    }
+
+    keyframes[keyframes.len() - 1].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_keyframes() {
+        let keyframes = [(1.0, 2.0), (7.0, 8.0), (9.0, 8.0)];
+
+        assert_eq!(sample_keyframes(&keyframes, 0.0), 2.0);
+        assert_eq!(sample_keyframes(&keyframes, 4.0), 5.0);
+        assert_eq!(sample_keyframes(&keyframes, 8.0), 8.0);
+        assert_eq!(sample_keyframes(&keyframes, 20.0), 8.0);
+    }
+
+    #[test]
+    fn test_level_progress() {
+        let curves = DifficultyCurves::default_curves();
+
+        assert_eq!(curves.level_progress(0), 0.0);
+        assert_eq!(curves.level_progress(5), 0.25);
+        assert_eq!(curves.level_progress(20), 0.0);
+    }
+
+    #[test]
+    fn test_difficulty_file_loads() {
+        data::load_ron::<DifficultyCurves>(DIFFICULTY_PATH).unwrap();
+    }
+}
@@ -1,9 +1,8 @@
 use bevy::prelude::*;
-use crate::{art, generic, AppState};
-
-const START_PLATFORM_HEIGHT: i32 = 2; // Platform height at the start of the game
-const POINTS_PER_LEVEL: i32 = 20; // The level number increments every POINTS_PER_LEVEL points
-const MAX_PLATFORM_HEIGHT: i32 = 8;
+use bevy::audio::{Pitch, PitchBundle, Volume};
+use std::time::Duration;
+use crate::{art, generic, settings, ui, AppState, GameCleanupEvent};
+use crate::input::Action;
 
 pub mod player;
 pub mod background;
@@ -11,6 +10,22 @@ pub mod camera;
 pub mod platforms;
 pub mod collisions;
 pub mod sprite_scaler;
+pub mod corridor;
+pub mod tiles;
+pub mod theme;
+pub mod difficulty;
+pub mod adaptive;
+pub mod stats;
+pub mod tutorial;
+
+#[cfg(test)]
+mod bench;
+
+const DIFFICULTY_EASE_SECONDS: f32 = 2.0; // Roughly how long player speed and platform gap take to reach new targets
+const LEVEL_UP_SOUND_HZ: f32 = 880.0;
+const LEVEL_UP_SOUND_SECONDS: f32 = 0.2;
+const TUTORIAL_GAP_MULTIPLIER: f32 = 1.4; // Platforms are spread further apart during the tutorial
+const TUTORIAL_SPEED_MULTIPLIER: f32 = 0.85;
 
 // Component attached to every entity which is apart of the game
 #[derive(Component)]
@@ -20,6 +35,7 @@ pub struct GameComponent;
 pub enum GameState {
     #[default]
     Running,
+    Countdown, // Held before the run starts or resumes, see ui::countdown
     Paused,
     GameOver,
 }
@@ -33,6 +49,12 @@ pub struct Game {
 #[derive(Event)]
 pub struct ScoreIncrease;
 
+// Sent when the score reaches a new level
+#[derive(Event)]
+pub struct LevelUp {
+    pub level: i32,
+}
+
 // Struct containing infromation regarding the games difficulty
 pub struct Difficulty {
     pub level: i32, // Other fields are derived from the level, which itself is derived from the score
@@ -41,6 +63,13 @@ pub struct Difficulty {
     pub platform_gap: generic::Range<f32>, // The min and max values that two platforms might spawn from each other on the y axis
     pub platform_height: i32, // Platform height in tiles
     pub player_max_speed: Vec2,
+    pub target_platform_gap: generic::Range<f32>, // platform_gap eases towards this
+    pub target_player_max_speed: Vec2, // player_max_speed eases towards this
+    pub platform_length_variance: f32, // Player widths the length of a platform can vary by
+    pub double_sided_chance: f32, // % Chance for a double sided platform to spawn for every platform spawn
+    pub floating_chance: f32, // % Chance for a floating platform to spawn when a double sided platform doesn't
+    pub adaptive_offset: f32, // Adjustment made by adaptive difficulty, see adaptive::AdaptiveDifficulty
+    pub steering_rate: f32, // Radians per second which the player turns when a steering button is held
 
     // Difficulty at the start of the game
     start_platform_gap: generic::Range<f32>,
@@ -64,71 +93,188 @@ impl Plugin for GamePlugin {
                 camera::CameraPlugin,
                 collisions::CollisionPlugin,
                 sprite_scaler::SpriteScalerPlugin,
+                tiles::TilesPlugin,
+                theme::ThemePlugin,
+                adaptive::AdaptivePlugin,
+                stats::RunStatsPlugin,
+                tutorial::TutorialPlugin,
             ))
 
+            .insert_resource(difficulty::DifficultyPreset::default())
+
             // Game resources have to be reset so the game can function properly if the user wants to play again
             .add_systems(OnEnter(AppState::GameSetup), insert_game_resouorces)
 
             .add_event::<ScoreIncrease>()
-            .add_systems(Update, recalculate_difficulty.run_if(in_state(AppState::Game)).run_if(in_state(GameState::Running)))
+            .add_event::<LevelUp>()
+            .add_systems(Update, (recalculate_difficulty, ease_difficulty).chain().run_if(in_state(AppState::Game)).run_if(in_state(GameState::Running)))
+            .add_systems(Update, play_level_up_sound.run_if(in_state(AppState::Game)))
+            .add_systems(Update, restart_on_action.run_if(in_state(AppState::Game)).run_if(in_state(ui::SubMenu::None)))
 
             .add_systems(OnEnter(AppState::GameCleanup), despawn_game_components);
 
     }
 }
 
-fn insert_game_resouorces(mut commands: Commands, scale_factor: Res<sprite_scaler::ScaleFactor>) {
+fn insert_game_resouorces(
+    mut commands: Commands,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+    preset: Res<difficulty::DifficultyPreset>,
+    settings: Res<settings::Settings>,
+    tutorial: Res<tutorial::Tutorial>,
+) {
     let player_world_height: f32 = art::PLAYER_SPRITE_SIZE.y * scale_factor.current;
+    let preset_multipliers = preset.multipliers().combine(&difficulty::control_scheme_multipliers(settings.control_scheme));
 
     // Difficulty Settings at the start of the game
-    let start_platform_gap: generic::Range<f32> = generic::Range {min: player_world_height * 2.2, max: player_world_height * 2.7};
-    let start_player_max_speed: Vec2 = Vec2::new(125.0 * scale_factor.current, 212.5 * scale_factor.current);
+    let start_platform_gap: generic::Range<f32> = generic::Range {
+        min: player_world_height * 2.2 * preset_multipliers.platform_gap,
+        max: player_world_height * 2.7 * preset_multipliers.platform_gap,
+    };
+    let start_player_max_speed: Vec2 = Vec2::new(125.0 * scale_factor.current, 212.5 * scale_factor.current) * preset_multipliers.player_speed;
+
+    // Difficulty curves are reloaded every game, so they can be tuned without recompiling
+    let difficulty_curves = difficulty::DifficultyCurves::load();
+
+    let mut difficulty = Difficulty {
+        level: 1,
+        platform_gap: start_platform_gap,
+        platform_height: 0,
+        player_max_speed: start_player_max_speed,
+        target_platform_gap: start_platform_gap,
+        target_player_max_speed: start_player_max_speed,
+        platform_length_variance: 0.0,
+        double_sided_chance: 0.0,
+        floating_chance: 0.0,
+        adaptive_offset: 0.0,
+        steering_rate: player::AUTO_MOVE_AV * preset_multipliers.steering_rate,
+        start_platform_gap: start_platform_gap,
+        start_player_max_speed: start_player_max_speed,
+    };
+    difficulty.apply_curves(&difficulty_curves, 0);
+    if tutorial.active() {
+        difficulty.make_gentle();
+    }
+    difficulty.snap_to_targets();
 
     commands.insert_resource(
         Game {
             score: 0,
-            difficulty: Difficulty {
-                level: 1,
-                platform_gap: start_platform_gap,
-                platform_height: START_PLATFORM_HEIGHT,
-                player_max_speed: start_player_max_speed,
-                start_platform_gap: start_platform_gap,
-                start_player_max_speed: start_player_max_speed,
-            }
+            difficulty,
         }
     );
+    commands.insert_resource(difficulty_curves);
 
 
     commands.insert_resource(platforms::Platforms::new());
     commands.insert_resource(background::BackgroundWallRows::new());
+    let seed = if tutorial.active() { tutorial::TUTORIAL_SEED } else { rand::random() };
+    commands.insert_resource(corridor::Corridor {seed});
 }
 
 // Recalculates difficulty variables based on the score
 // Only fully runs when the score updates
-pub fn recalculate_difficulty(mut game: ResMut<Game>, mut score_increase: EventReader<ScoreIncrease>) {
+pub fn recalculate_difficulty(
+    mut game: ResMut<Game>,
+    mut score_increase: EventReader<ScoreIncrease>,
+    mut level_up: EventWriter<LevelUp>,
+    difficulty_curves: Res<difficulty::DifficultyCurves>,
+    tutorial: Res<tutorial::Tutorial>,
+) {
     for _ in score_increase.read() {
-
         let score = game.score;
-        let difficulty = &mut game.difficulty;
-        difficulty.level = (score / POINTS_PER_LEVEL) + 1;
-        let level = difficulty.level;
+        let previous_level = game.difficulty.level;
+        game.difficulty.apply_curves(&difficulty_curves, score);
+        if tutorial.active() {
+            game.difficulty.make_gentle();
+        }
+
+        if game.difficulty.level > previous_level {
+            level_up.send(LevelUp {level: game.difficulty.level});
+        }
+    }
+}
+
+// Move player speed and platform gap towards their targets, so the difficulty ramps up instead of jumping
+fn ease_difficulty(mut game: ResMut<Game>, time: Res<Time>) {
+    // Exponential easing, covers ~95% of the distance to the target in DIFFICULTY_EASE_SECONDS
+    let t = 1.0 - (-3.0 * time.delta_seconds() / DIFFICULTY_EASE_SECONDS).exp();
+    game.difficulty.ease_towards_targets(t);
+}
+
+// Restart the game from anywhere in a run when the restart action is pressed
+fn restart_on_action(
+    actions: Res<Input<Action>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut cleanup_event: EventWriter<GameCleanupEvent>,
+) {
+    if actions.just_pressed(Action::Restart) {
+        cleanup_event.send(GameCleanupEvent{next_state: AppState::GameSetup});
+        next_state.set(AppState::GameCleanup);
+    }
+}
+
+// Short chime whenever a new level is reached
+fn play_level_up_sound(
+    mut commands: Commands,
+    mut level_up: EventReader<LevelUp>,
+    mut pitch_assets: ResMut<Assets<Pitch>>,
+    settings: Res<settings::Settings>,
+) {
+    for _ in level_up.read() {
+        commands.spawn(PitchBundle {
+            source: pitch_assets.add(Pitch::new(LEVEL_UP_SOUND_HZ, Duration::from_secs_f32(LEVEL_UP_SOUND_SECONDS))),
+            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new_relative(settings.sfx_volume)),
+        });
+    }
+}
+
+impl Difficulty {
+    // Set difficulty variables from the difficulty curves at a score
+    pub fn apply_curves(&mut self, curves: &difficulty::DifficultyCurves, score: i32) {
+        self.level = (score / curves.points_per_level.max(1)) + 1;
+        let level = self.level;
 
         // Even though the difficulty should ramp over time the platforms move further apart
         // This is to make it so that the player can reasonably navigate between platforms at high speeds
-        let platform_gap_multiplier = (level as f32 * 0.05) + 1.0;
-        difficulty.platform_gap.min = difficulty.start_platform_gap.min * platform_gap_multiplier;
-        difficulty.platform_gap.max = difficulty.start_platform_gap.max * platform_gap_multiplier;
-
-        // Increment platform height every new level untill it maxes out
-        difficulty.platform_height = START_PLATFORM_HEIGHT - 1 + level;
-        if difficulty.platform_height > MAX_PLATFORM_HEIGHT {
-            difficulty.platform_height = MAX_PLATFORM_HEIGHT;
-        }
-        
+        // A negative adaptive offset also spreads platforms out further, to make the game easier
+        let platform_gap_multiplier = curves.platform_gap.sample(level, score) * (1.0 - self.adaptive_offset.min(0.0));
+        self.target_platform_gap.min = self.start_platform_gap.min * platform_gap_multiplier;
+        self.target_platform_gap.max = self.start_platform_gap.max * platform_gap_multiplier;
+
+        self.platform_height = (curves.platform_height.sample(level, score).round() as i32).max(1);
+
         // Increase player speed
-        let player_speed_multiplier = (level as f32 * 0.07) + 1.0;
-        difficulty.player_max_speed.x = difficulty.start_player_max_speed.x * player_speed_multiplier;
-        difficulty.player_max_speed.y = difficulty.start_player_max_speed.y * player_speed_multiplier;
+        let player_speed_multiplier = curves.player_speed.sample(level, score) * (1.0 + self.adaptive_offset);
+        self.target_player_max_speed = self.start_player_max_speed * player_speed_multiplier;
+
+        self.platform_length_variance = curves.platform_length_variance.sample(level, score).max(0.0);
+        self.double_sided_chance = curves.double_sided_chance.sample(level, score);
+        self.floating_chance = curves.floating_chance.sample(level, score);
+    }
+
+    // Flat difficulty for the tutorial, with only wall platforms which alternate sides
+    pub fn make_gentle(&mut self) {
+        self.target_platform_gap.min = self.start_platform_gap.min * TUTORIAL_GAP_MULTIPLIER;
+        self.target_platform_gap.max = self.start_platform_gap.max * TUTORIAL_GAP_MULTIPLIER;
+        self.target_player_max_speed = self.start_player_max_speed * TUTORIAL_SPEED_MULTIPLIER;
+
+        self.platform_height = 1;
+        self.platform_length_variance = 0.0;
+        self.double_sided_chance = 0.0;
+        self.floating_chance = 0.0;
+    }
+
+    // Move player speed and platform gap a fraction t of the way to their targets
+    pub fn ease_towards_targets(&mut self, t: f32) {
+        self.platform_gap.min += (self.target_platform_gap.min - self.platform_gap.min) * t;
+        self.platform_gap.max += (self.target_platform_gap.max - self.platform_gap.max) * t;
+        self.player_max_speed = self.player_max_speed.lerp(self.target_player_max_speed, t);
+    }
+
+    // Jump straight to the targets, used before a game starts
+    pub fn snap_to_targets(&mut self) {
+        self.ease_towards_targets(1.0);
     }
 }
 
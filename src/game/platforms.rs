@@ -1,11 +1,12 @@
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use std::f32::consts::PI;
 use crate::{art, generic, AppState, game};
-use game::sprite_scaler;
+use game::{sprite_scaler, corridor, tiles, theme, tutorial};
 use rand::Rng;
 use generic::Direction;
 
-const DOUBLE_SIDED_PLATFORM_CHANCE: usize = 10; // % Change for a double sided platform to spawn for every platform spawn
+const NEAR_MISS_PLAYER_WIDTHS: f32 = 0.5; // Passing a platform closer than this many player widths is a near miss
 
 // For spawning the first platform
 const FIRST_PLATFORM_SIDE: Direction = Direction::Left;
@@ -15,20 +16,55 @@ const FIRST_PLATFORM_Y: f32 = 1.0 / 4.0; // How far up the screen the first plat
 #[derive(Resource)]
 pub struct Platforms { 
     pub total_platforms: u32, // The total number of platforms spawned
+    pub total_rows: u32, // The total number of platform rows spawned
+    pub rows_passed: u32, // The number of platform rows the player has flown past
     pub platforms_vec: Vec<Platform>, // Contains every existing platform
 } 
 
 #[derive(Component, Copy, Clone)]
 pub struct Platform {
     pub index: u32,
+    pub row: u32, // Platforms spawned at the same height share a row, and are passed together
+    pub double_sided: bool, // True for both platforms of a double sided platform
     pub hitbox: [generic::Range<f32>; 2], // x and y hitbox
     pub dimensions: [i32; 2], // x and y platform dimensions
-    pub side: generic::Direction, // Side of the screen the platform is spawned from
+    pub anchor: PlatformAnchor, // Where the platform is attached to the level
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PlatformAnchor {
+    Wall(Direction), // Grows out of the wall on this side of the screen
+    Floating { offset: i32 }, // Floats in the middle of the level, offset is how many tiles it is moved right of the center
+}
+
+impl PlatformAnchor {
+    // Side of the screen the next wall platform should be spawned from
+    // Wall platforms alternate sides, after a floating platform either side is fine
+    pub fn next_side(&self) -> Direction {
+        match self {
+            PlatformAnchor::Wall(side) => side.reverse(),
+            PlatformAnchor::Floating { .. } => Direction::random(),
+        }
+    }
 }
 
 impl Platforms {
     pub fn new() -> Self {
-        Platforms {total_platforms: 0, platforms_vec: Vec::new()}
+        Platforms {total_platforms: 0, total_rows: 0, rows_passed: 0, platforms_vec: Vec::new()}
+    }
+}
+
+// Sent when the player flies below a row of platforms
+#[derive(Event)]
+pub struct PlatformPassed {
+    pub index: u32, // Row of the passed platforms
+    pub double_sided: bool,
+    pub margin: f32, // Horizontal distance between the player and the closest platform in the row
+}
+
+impl PlatformPassed {
+    pub fn is_near_miss(&self, scale_factor: f32) -> bool {
+        self.margin < art::PLAYER_SPRITE_SIZE.x * scale_factor * NEAR_MISS_PLAYER_WIDTHS
     }
 }
 
@@ -37,32 +73,44 @@ pub struct PlatformPlugin;
 impl Plugin for PlatformPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<PlatformPassed>()
             .add_systems(OnEnter(AppState::Game), spawn_platforms)
-            .add_systems(Update, (spawn_platforms, despawn_platforms.after(spawn_platforms)).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
+            .add_systems(Update, (spawn_platforms, despawn_platforms.after(spawn_platforms)).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)))
+            .add_systems(Update, pass_platforms.after(game::player::move_player).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
     }
 }
 
 // Responsible for spawning new platforms when needed
 // Platform dimensions and location are specified by game difficulty parameters
-fn spawn_platforms(
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_platforms(
     screen_information: Res<generic::ScreenInformation>,
     mut platforms: ResMut<Platforms>,
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    tile_art: Res<tiles::TileArt>,
+    active_theme: Res<theme::ActiveTheme>,
     game: Res<game::Game>,
+    corridor: Res<corridor::Corridor>,
     scale_factor: Res<sprite_scaler::ScaleFactor>,
+    tutorial: Res<tutorial::Tutorial>,
 ) {
     let difficulty = &game.difficulty;
     let mut rng = rand::thread_rng();
 
-    // Calculate long a platform would need to be to cross the entire screen
-    let platform_world_width = art::PLATFORM_SPRITE_SIZE.x * scale_factor.current;
-    let platforms_across_screen: i32 = ((screen_information.window_width - (screen_information.x_deadspace * 2.0)) / platform_world_width) as i32;
+    let platform_world_size = art::PLATFORM_SPRITE_SIZE * scale_factor.current;
+    let platform_world_width = platform_world_size.x;
+    let wall_world_size = art::WALL_SPRITE_SIZE * scale_factor.current;
 
     // Continually add platforms untill they fill slightly beyond the visible area
     let mut sufficient_platforms = false;
     while !sufficient_platforms {
-        let platform_gap = rng.gen_range(difficulty.platform_gap.min..difficulty.platform_gap.max); // Generate random platform gap
+        // Generate random platform gap, the tutorial always uses the middle of the range so its layout is scripted
+        let platform_gap = if tutorial.active() {
+            (difficulty.platform_gap.min + difficulty.platform_gap.max) / 2.0
+        } else {
+            rng.gen_range(difficulty.platform_gap.min..difficulty.platform_gap.max)
+        };
         
         // Get last platform if it exists
         // Otherwise create an imaginary last platform, whose data will be used to spawn the first platform in the correct position
@@ -72,12 +120,14 @@ fn spawn_platforms(
         } else {
             last_platform = Platform {
                 index: 0,
+                row: 0,
+                double_sided: false,
                 hitbox: [
                     generic::Range {min: 0.0, max: 0.0}, // X
                     generic::Range {min: (screen_information.window_height * FIRST_PLATFORM_Y) + platform_gap, max: 0.0}, // Y
                 ],
                 dimensions: [0, 0],
-                side: FIRST_PLATFORM_SIDE.reverse(),
+                anchor: PlatformAnchor::Wall(FIRST_PLATFORM_SIDE.reverse()),
             };
         }
 
@@ -85,22 +135,48 @@ fn spawn_platforms(
 
         // How many platform tiles length each platform can vary from the last
         let platform_sprites_per_player_sprite: i32 = ((art::PLAYER_SPRITE_SIZE.x * scale_factor.current) / platform_world_width) as i32;
+        let platform_length_variance_tiles = (platform_sprites_per_player_sprite as f32 * difficulty.platform_length_variance).round() as i32;
         let platform_length_variance: generic::Range<i32> = generic::Range {
-            min: platform_sprites_per_player_sprite * -1,
-            max: platform_sprites_per_player_sprite * 1,
+            min: -platform_length_variance_tiles,
+            max: platform_length_variance_tiles,
         };
 
         // Only spawn a new platform when the last platform is close to the visible area
         if last_platform_y_min > screen_information.y_visible_area.min - (platform_gap * 4.0) {
 
+            // The level can be narrower or wider where the platform spawns
+            // Platform lengths are based on the narrowest part of the level the platform spans
+            // Wall platforms are anchored to the widest part, so there is never a gap between the platform and the wall
+            let platform_y_range = generic::Range {
+                min: last_platform_y_min - platform_gap - (platform_world_size.y * difficulty.platform_height as f32),
+                max: last_platform_y_min - platform_gap,
+            };
+            let (narrow_bounds, wide_bounds) = corridor.bounds_between(platform_y_range, &screen_information, wall_world_size);
+
+            // Calculate long a platform would need to be to cross the entire level
+            let platforms_across_screen: i32 = ((narrow_bounds.max - narrow_bounds.min) / platform_world_width) as i32;
+
+            // Tiles a wall platform needs to be extended by to reach from the widest part of the level to the narrowest
+            let wall_extension = |side: Direction| -> i32 {
+                match side {
+                    Direction::Left => ((narrow_bounds.min - wide_bounds.min) / platform_world_width).round() as i32,
+                    Direction::Right => ((wide_bounds.max - narrow_bounds.max) / platform_world_width).round() as i32,
+                }
+            };
+
             // Calculate new platform dimensions
+            let platform_length_offset = if platform_length_variance.max > 0 {
+                rng.gen_range(platform_length_variance.min..platform_length_variance.max)
+            } else {
+                0
+            };
             let platform_dimensions = [
-                platforms_across_screen / 2 + rng.gen_range(platform_length_variance.min..platform_length_variance.max),
+                platforms_across_screen / 2 + platform_length_offset,
                 difficulty.platform_height,
             ];
 
-            // This platform will spawn on the opposite screen side to the previous platform
-            let platform_side = last_platform.side.reverse();
+            // Wall platforms spawn on the opposite screen side to the previous platform
+            let platform_side = last_platform.anchor.next_side();
 
             // Create an uncomplete platform hitbox
             // The only information which needs to be known right now is the top of the platform
@@ -112,13 +188,50 @@ fn spawn_platforms(
             
             let platform = Platform {
                 index: platforms.total_platforms,
+                row: platforms.total_rows,
+                double_sided: false,
                 hitbox: platform_hitbox,
                 dimensions: platform_dimensions,
-                side: platform_side,
+                anchor: PlatformAnchor::Wall(platform_side),
+            };
+
+            // Spawn 2 platforms or a floating platform with the chances given by the difficulty
+            let double_sided = rng.gen_range(0.0..100.0) < difficulty.double_sided_chance;
+            let floating = !double_sided && rng.gen_range(0.0..100.0) < difficulty.floating_chance;
+
+            // Leave a gap on both sides of a floating platform which is at least 2 players wide
+            // When the level is too narrow for that a wall platform spawns instead
+            let min_side_gap = platform_sprites_per_player_sprite * 2;
+            let floating_length = if floating {
+                floating_platform_length(platform_dimensions[0], platforms_across_screen, min_side_gap)
+            } else {
+                None
             };
 
-            // Spawn 2 platforms DOUBLE_SIDED_PLATFORM_CHANCE % of times
-            let spawn_platforms = if rng.gen_range(1..100) < DOUBLE_SIDED_PLATFORM_CHANCE {
+            if let Some(floating_length) = floating_length {
+
+                // Randomly shift the platform left or right with whatever space is left over
+                let max_offset = (platforms_across_screen - floating_length) / 2 - min_side_gap;
+                let offset = if max_offset > 0 {
+                    rng.gen_range(-max_offset..=max_offset)
+                } else {
+                    0
+                };
+
+                let floating_platform = Platform {
+                    dimensions: [floating_length, platform_dimensions[1]],
+                    anchor: PlatformAnchor::Floating { offset },
+                    ..platform
+                };
+
+                draw_platform(floating_platform, narrow_bounds, &mut commands, &mut meshes, &tile_art.sets[active_theme.index], &mut platforms, &screen_information, scale_factor.current);
+                platforms.total_rows += 1;
+                continue;
+            }
+
+            // Skip the second platform of a double sided platform when the level is too narrow for it
+            let second_platform_length = platforms_across_screen - platform_dimensions[0] - (platform_sprites_per_player_sprite * 2);
+            let spawn_platforms = if double_sided && second_platform_length > 0 {
                 2
             } else {
                 1
@@ -132,19 +245,28 @@ fn spawn_platforms(
                 if i > 0 {
                     current_platform = Platform {
                         index: platforms.total_platforms,
+                        row: platform.row,
+                        double_sided: true,
                         hitbox: platform_hitbox,
                         dimensions: [
-                            platforms_across_screen - platform_dimensions[0] - (platform_sprites_per_player_sprite * 2),
+                            second_platform_length + wall_extension(platform_side.reverse()),
                             platform_dimensions[1],
                         ],
-                        side: platform_side.reverse(),
+                        anchor: PlatformAnchor::Wall(platform_side.reverse()),
                     }
                 } else {
-                    current_platform = platform;
+                    current_platform = Platform {
+                        dimensions: [platform_dimensions[0] + wall_extension(platform_side), platform_dimensions[1]],
+                        double_sided: spawn_platforms > 1,
+                        ..platform
+                    };
                 }
 
-                draw_platform(current_platform, &mut commands, &asset_server, &mut platforms, &screen_information, scale_factor.current)
+                draw_platform(current_platform, wide_bounds, &mut commands, &mut meshes, &tile_art.sets[active_theme.index], &mut platforms, &screen_information, scale_factor.current)
             }
+
+            // Both platforms of a double sided platform are one row
+            platforms.total_rows += 1;
             
 
         } else {
@@ -160,11 +282,16 @@ fn spawn_platforms(
         
 }
 
-// Draws a platform on either the left or right side of the level with a specified size
+// Draws a platform on the left or right side of the level, or floating in the middle of it, with a specified size
+// level_bounds are the x coordinates of the walls the platform is placed between
+// Every tile is added to one mesh, so each platform is a single entity
+#[allow(clippy::too_many_arguments)]
 fn draw_platform(
     mut platform: Platform,
+    level_bounds: generic::Range<f32>,
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
+    meshes: &mut Assets<Mesh>,
+    tile_set: &tiles::TileSet,
     platforms: &mut ResMut<Platforms>,
     screen_information: &generic::ScreenInformation,
     scale_factor: f32
@@ -174,61 +301,83 @@ fn draw_platform(
         art::PLATFORM_SPRITE_SIZE.y * scale_factor,
     );
 
-    // Determine location of the first sprite, and set tile rotations which change depending on which side of the screen the platform is spawned on
+    // Determine location of the first sprite, the direction the platform grows in and the columns which form the platforms free ends
+    // Free ends are made from corner and side pieces, the tiles between them only have a top and bottom
     let sprite_spawn_x: f32;
-    let straight_tile_rotations: [f32; 3];
-    let corner_tile_rotations: [f32; 3];
     let x_direction: f32;
-    
-    if platform.side == Direction::Left {
-        sprite_spawn_x = screen_information.x_deadspace +  (platform_world_size.x / 2.0);
-        straight_tile_rotations = [0.0, 1.5, 1.0];
-        corner_tile_rotations = [1.5, 1.0, 0.0];
-        x_direction = 1.0; // Platform grows into positive x direction
-    } else {
-        sprite_spawn_x = screen_information.window_width - screen_information.x_deadspace -  (platform_world_size.x / 2.0);
-        straight_tile_rotations = [0.0, 0.5, 1.0];
-        corner_tile_rotations = [0.0, 0.5, 0.0];
-        x_direction = -1.0; // Platform grows into negative x direction
+    let free_ends: Vec<(i32, Direction)>; // Column of the end and the direction it faces
+    let last_column = platform.dimensions[0] - 1;
+
+    match platform.anchor {
+        PlatformAnchor::Wall(Direction::Left) => {
+            sprite_spawn_x = level_bounds.min + (platform_world_size.x / 2.0);
+            x_direction = 1.0; // Platform grows into positive x direction
+            free_ends = vec![(last_column, Direction::Right)];
+        },
+        PlatformAnchor::Wall(Direction::Right) => {
+            sprite_spawn_x = level_bounds.max - (platform_world_size.x / 2.0);
+            x_direction = -1.0; // Platform grows into negative x direction
+            free_ends = vec![(last_column, Direction::Left)];
+        },
+        PlatformAnchor::Floating { offset } => {
+            let center_x = (level_bounds.min + level_bounds.max) / 2.0 + offset as f32 * platform_world_size.x;
+            sprite_spawn_x = center_x - (platform.dimensions[0] as f32 * platform_world_size.x / 2.0) + (platform_world_size.x / 2.0);
+            x_direction = 1.0;
+            free_ends = vec![(0, Direction::Left), (last_column, Direction::Right)];
+        },
     }
 
-    let tile_rotations = [straight_tile_rotations, corner_tile_rotations];
     let first_sprite_location = Vec2::new(sprite_spawn_x, platform.hitbox[1].max - (platform_world_size.y / 2.0));
 
     // Calculate the rest of the platform hitbox (allready given y max from the parent function)
-    platform.hitbox[0] = generic::Range { // X range
-        max: first_sprite_location.x + (platform.dimensions[0] as f32 * platform_world_size.x * x_direction),
-        min: first_sprite_location.x,
+    // The wall side of a wall platform extends to the edge of the screen, so the platform and wall hitboxes never leave a gap
+    platform.hitbox[0] = match platform.anchor { // X range
+        PlatformAnchor::Wall(Direction::Left) => generic::Range {
+            min: 0.0,
+            max: first_sprite_location.x + (platform.dimensions[0] as f32 * platform_world_size.x),
+        },
+        PlatformAnchor::Wall(Direction::Right) => generic::Range {
+            min: first_sprite_location.x - (platform.dimensions[0] as f32 * platform_world_size.x),
+            max: screen_information.window_width,
+        },
+        PlatformAnchor::Floating { .. } => generic::Range {
+            min: first_sprite_location.x - (platform_world_size.x / 2.0),
+            max: first_sprite_location.x + (platform.dimensions[0] as f32 * platform_world_size.x) - (platform_world_size.x / 2.0),
+        },
     };
     platform.hitbox[1].min = platform.hitbox[1].max - (platform_world_size.y * platform.dimensions[1] as f32); // Y min
 
-    // Spawn all tile pieces of the platform
+    // Add all tile pieces of the platform to the platform mesh
+    let mut platform_mesh = tiles::TileMeshBuilder::default();
     for x in 0..platform.dimensions[0] {
+
+        // Direction the end faces when this column is a free end of the platform
+        let end_facing = free_ends.iter().find(|(column, _)| *column == x).map(|(_, facing)| *facing);
+
+        // Top and bottom straight tiles are rotated the same way no matter which direction the ends face
+        let tile_rotations = end_tile_rotations(end_facing.unwrap_or(Direction::Right));
+
         for y in 0..platform.dimensions[1] {
 
             // Skip coordinates where sprites should not be spawned
             let y_middle = y > 0 && y < platform.dimensions[1] - 1; // True if the current y coordinate is in the middle of the platform (where no sprite should be)
-            if x < platform.dimensions[0] - 1 && y_middle {
+            if end_facing.is_none() && y_middle {
                 continue;
             }
 
-            // Where the sprite should be spawned
-            let sprite_location = Vec2::new(first_sprite_location.x + (x_direction * platform_world_size.x * x as f32), first_sprite_location.y - (platform_world_size.y * y as f32));
+            // Where the tile should be placed
+            let tile_location = Vec2::new(first_sprite_location.x + (x_direction * platform_world_size.x * x as f32), first_sprite_location.y - (platform_world_size.y * y as f32));
             
-            // Set the sprite to a corner or straight piece based on the x and y coordinate
-            let sprite_path: &str;
-            let tile_index: usize;
-            if x == platform.dimensions[0] - 1 && (y == 0 || y == platform.dimensions[1] - 1) { // Detect corner piece
-                sprite_path = art::PLATFORM_CORNER_SPRITE_PATH;
-                tile_index = 1;
+            // Set the tile to a corner or straight piece based on the x and y coordinate
+            let tile_index: usize = if end_facing.is_some() && (y == 0 || y == platform.dimensions[1] - 1) { // Detect corner piece
+                1
             } else { // Detect straight piece
-                sprite_path = art::PLATFORM_STRAIGHT_SPRITE_PATH;
-                tile_index = 0;
-            }
+                0
+            };
 
             // Determine rotation index from x and y coordinates
             let rotation_index: usize;
-            if x < platform.dimensions[0] - 1 {
+            if end_facing.is_none() {
                 if y == platform.dimensions[1] - 1 {
                     rotation_index = 2;
                 } else {
@@ -246,40 +395,61 @@ fn draw_platform(
                 }
             }
 
-            // Spawn sprite
-            commands.spawn(
-                (
-                    game::GameComponent,
-                    platform,
-                    SpriteBundle {
-                        texture: asset_server.load(sprite_path),
-                        transform: Transform {
-                            translation: Vec3::new(sprite_location.x, sprite_location.y, 1.0),
-                            rotation: Quat::from_rotation_z(PI * tile_rotations[tile_index][rotation_index]),
-                            scale: Vec3::splat(scale_factor),
-                        },
-                        ..default()
-                    },
-                )
+            platform_mesh.push_tile(
+                tile_location,
+                platform_world_size,
+                PI * tile_rotations[tile_index][rotation_index],
+                tiles::PLATFORM_TILE_UVS[tile_index],
             );
         }
     }
 
+    // Spawn platform
+    commands.spawn(
+        (
+            game::GameComponent,
+            platform,
+            MaterialMesh2dBundle {
+                mesh: meshes.add(platform_mesh.build()).into(),
+                material: tile_set.platform_material.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                ..default()
+            },
+        )
+    );
+
     // Update platforms vec with new platform
     platforms.platforms_vec.push(platform);
     platforms.total_platforms += 1;
 }
 
+// Tile rotations (in multiples of PI) for the end of a platform facing the given direction
+// Indexed by [straight, corner][rotation index]
+fn end_tile_rotations(facing: Direction) -> [[f32; 3]; 2] {
+    match facing {
+        Direction::Right => [[0.0, 1.5, 1.0], [1.5, 1.0, 0.0]],
+        Direction::Left => [[0.0, 0.5, 1.0], [0.0, 0.5, 0.0]],
+    }
+}
+
+// Length in tiles of a floating platform which leaves at least min_side_gap tiles free on both sides
+// None when the level is too narrow to fit a platform at least 2 tiles long between the gaps
+fn floating_platform_length(length: i32, platforms_across_screen: i32, min_side_gap: i32) -> Option<i32> {
+    let max_length = platforms_across_screen - min_side_gap * 2;
+    if max_length < 2 {
+        return None;
+    }
+
+    Some(length.clamp(2, max_length))
+}
+
 
 // Despawn platforms which have gone out of the visible area
-// Also increase the score when a platform is despawned
-fn despawn_platforms(
+pub fn despawn_platforms(
     mut commands: Commands,
     platform_query: Query<(Entity, &Platform)>,
     mut platforms: ResMut<Platforms>,
-    mut game: ResMut<game::Game>,
     screen_information: Res<generic::ScreenInformation>,
-    mut score_increase: EventWriter<game::ScoreIncrease>
 ) {
     let mut removed_indices: Vec<u32> = Vec::new(); // Platform indices removed from the platforms vec
 
@@ -289,12 +459,82 @@ fn despawn_platforms(
 
             // Only remove this platform from the platforms vec once (because there is only one entry per platform)
             if !removed_indices.contains(&platform.index) {
-                game.score += 1;
-                score_increase.send(game::ScoreIncrease);
-
                 removed_indices.push(platform.index);
                 platforms.platforms_vec.remove(0);
             }   
         }
     }
-}
\ No newline at end of file
+}
+
+// Increase the score when the player flies below a row of platforms
+pub fn pass_platforms(
+    player_query: Query<&Transform, With<game::player::Player>>,
+    mut platforms: ResMut<Platforms>,
+    mut game: ResMut<game::Game>,
+    mut platform_passed: EventWriter<PlatformPassed>,
+    mut score_increase: EventWriter<game::ScoreIncrease>,
+) {
+    if let Ok(player_transform) = player_query.get_single() {
+        let player_translation = player_transform.translation;
+
+        // Platforms are stored in the order they were spawned, so rows are passed in order
+        let mut passed: Option<PlatformPassed> = None;
+        for platform in platforms.platforms_vec.iter().filter(|platform| platform.row >= platforms.rows_passed) {
+            if player_translation.y > platform.hitbox[1].min {
+                break;
+            }
+
+            // Distance from the player to the edge of the platform
+            let margin = if player_translation.x < platform.hitbox[0].min {
+                platform.hitbox[0].min - player_translation.x
+            } else {
+                player_translation.x - platform.hitbox[0].max
+            };
+
+            if let Some(passed) = &mut passed {
+                if passed.index == platform.row {
+                    passed.margin = passed.margin.min(margin);
+                    continue;
+                }
+            }
+
+            // A new row has been passed, finish the last one
+            if let Some(passed) = passed.take() {
+                send_platform_passed(passed, &mut game, &mut platform_passed, &mut score_increase);
+            }
+            passed = Some(PlatformPassed {index: platform.row, double_sided: platform.double_sided, margin});
+        }
+
+        if let Some(passed) = passed {
+            platforms.rows_passed = passed.index + 1;
+            send_platform_passed(passed, &mut game, &mut platform_passed, &mut score_increase);
+        }
+    }
+}
+
+fn send_platform_passed(
+    passed: PlatformPassed,
+    game: &mut game::Game,
+    platform_passed: &mut EventWriter<PlatformPassed>,
+    score_increase: &mut EventWriter<game::ScoreIncrease>,
+) {
+    game.score += 1;
+    score_increase.send(game::ScoreIncrease);
+    platform_passed.send(passed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floating_platform_leaves_side_gaps() {
+        assert_eq!(floating_platform_length(6, 20, 4), Some(6));
+        assert_eq!(floating_platform_length(16, 20, 4), Some(12));
+        assert_eq!(floating_platform_length(1, 20, 4), Some(2));
+
+        // A narrow corridor has no room for the gaps, so a wall platform spawns instead
+        assert_eq!(floating_platform_length(5, 9, 4), None);
+        assert_eq!(floating_platform_length(5, 10, 4), Some(2));
+    }
+}
@@ -1,21 +1,46 @@
 use bevy::prelude::*;
-use crate::{art, generic, game, AppState};
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use crate::{art, generic, game, input, settings, AppState};
+use input::{Action, StickSteering, PointerSteering};
 use game::sprite_scaler;
 use generic::Direction;
 use std::f32::consts::PI;
 
-const START_DIRECTION: Direction = Direction::Right; // Direction player starts facing
+pub const START_DIRECTION: Direction = Direction::Right; // Direction player starts facing
 const DEATH_ANIMATION_FPS: f32 = 8.0;
 
-const AUTO_MOVE_AV: f32 = 6.0; // Radians per second which the player turns when a steering button is held
+pub const AUTO_MOVE_AV: f32 = 6.0; // Radians per second which the player turns when a steering button is held
 const ANGLE_RANGE_RAD: generic::Range<f32> = generic::Range {min: PI / -2.0, max: PI / 2.0}; // Miniumum and maximum angle for player
+const POINTER_FULL_TURN_PLAYER_WIDTHS: f32 = 3.0; // How far the pointer has to be from the plane to turn it all the way
+const ONE_BUTTON_TURN_FRACTION: f32 = 0.6; // Fraction of the max angle a one button tap turns the player to on the other side
+const ONE_BUTTON_TAP_SECONDS: f32 = 0.25; // Presses shorter than this are taps, longer presses only turn while held
+const INERTIA_ACCELERATION: f32 = 2.0; // Max speeds per second the players speed can change by with the inertia flight model
+
+// How the players speed is worked out from their angle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FlightModel {
+    #[default]
+    Stepped, // Speed changes in steps, one for each player sprite, the original feel
+    Continuous, // Speed follows the angle exactly
+    Inertia, // Speed accelerates towards the speed for the angle
+}
 
 #[derive(Component, Debug)]
 pub struct Player {
-    pub speed: Vec2, // X and y speed
+    pub speed: Vec2, // X and y speed, x is negative when moving left
 
     facing: Direction, // Direction the player is currently facing
     angle_rad: f32, 
+    one_button_turn: OneButtonTurn,
+}
+
+// Turn in progress with the one button control scheme
+#[derive(Debug, Default)]
+struct OneButtonTurn {
+    direction: f32, // -1 to turn left, 1 to turn right
+    held_seconds: f32,
+    target_rad: Option<f32>, // Angle a tap is still turning towards
 }
 
 #[derive(Component)]
@@ -32,7 +57,32 @@ impl Plugin for PlayerPlugin {
             .insert_resource(DeathAnimationTimer(Timer::from_seconds(1.0 / DEATH_ANIMATION_FPS, TimerMode::Repeating)))
             .add_systems(OnEnter(AppState::Game), spawn_player)
             .add_systems(Update, animate_death.run_if(in_state(game::GameState::GameOver)))
-            .add_systems(Update, (change_angle, set_player_heading, calculate_speed, move_player).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
+            .add_systems(Update, (update_pointer_steering, one_button_steering, change_angle, set_player_heading, calculate_speed, move_player).chain().run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
+    }
+}
+
+impl Player {
+    // Direction the one button is turning the player while it is held, -1 for left and 1 for right
+    pub fn one_button_turn_direction(&self) -> f32 {
+        self.one_button_turn.direction
+    }
+}
+
+impl FlightModel {
+    pub fn next(&self) -> Self {
+        match self {
+            FlightModel::Stepped => FlightModel::Continuous,
+            FlightModel::Continuous => FlightModel::Inertia,
+            FlightModel::Inertia => FlightModel::Stepped,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlightModel::Stepped => "Stepped",
+            FlightModel::Continuous => "Continuous",
+            FlightModel::Inertia => "Inertia",
+        }
     }
 }
 
@@ -81,6 +131,7 @@ fn spawn_player(
                     Direction::Left => ANGLE_RANGE_RAD.min,
                     Direction::Right => ANGLE_RANGE_RAD.max,
                 },
+                one_button_turn: OneButtonTurn::default(),
             },
 
             SpriteSheetBundle {
@@ -117,33 +168,55 @@ fn spawn_player(
     );
 }
 
-// Changes the players angle with the steering keys
+// Changes the players angle with the steering actions
 fn change_angle(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
+    stick_steering: Res<StickSteering>,
+    pointer_steering: Res<PointerSteering>,
     mut player_query: Query<(&mut Player, &TextureAtlasSprite)>,
+    game: Res<game::Game>,
+    settings: Res<settings::Settings>,
     time: Res<Time>,
 ) {
-    if let Ok((player, sprite)) = &mut player_query.get_single_mut() {
+    // The one button scheme only steers with its own button, see one_button_steering
+    if settings.control_scheme == input::ControlScheme::OneButton {
+        return;
+    }
 
-        let rad_per_sprite = ANGLE_RANGE_RAD.max / art::PLAYER_SPRITESHEET_INDICES as f32;
-        let rad_from_sprite_index = generic::reverse_index(sprite.index, art::PLAYER_SPRITESHEET_INDICES) as f32 * rad_per_sprite * player.facing.to_x();
+    if let Ok((player, sprite)) = &mut player_query.get_single_mut() {
 
         // Immediately jump to the next angle_rad which corresponds to a spritesheet index
         // This is done to give immideate feedback to the player
-        if keyboard_input.just_pressed(KeyCode::D) || keyboard_input.just_pressed(KeyCode::Right) {
-            player.angle_rad = rad_from_sprite_index + rad_per_sprite;
-        }
-        if keyboard_input.just_pressed(KeyCode::A) || keyboard_input.just_pressed(KeyCode::Left) {
-            player.angle_rad = rad_from_sprite_index - rad_per_sprite;
+        // Only the stepped flight model does this, as it is the only one where speed depends on the sprite
+        if settings.flight_model == FlightModel::Stepped {
+            let rad_per_sprite = ANGLE_RANGE_RAD.max / art::PLAYER_SPRITESHEET_INDICES as f32;
+            let rad_from_sprite_index = generic::reverse_index(sprite.index, art::PLAYER_SPRITESHEET_INDICES) as f32 * rad_per_sprite * player.facing.to_x();
+
+            if actions.just_pressed(Action::SteerRight) {
+                player.angle_rad = rad_from_sprite_index + rad_per_sprite;
+            }
+            if actions.just_pressed(Action::SteerLeft) {
+                player.angle_rad = rad_from_sprite_index - rad_per_sprite;
+            }
         }
         
 
         // Change player angle_rad in the direction of the steering keypress
-        if keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right) {
-            player.angle_rad += AUTO_MOVE_AV / 1.0 * time.delta_seconds();
+        if actions.pressed(Action::SteerRight) {
+            player.angle_rad += game.difficulty.steering_rate * time.delta_seconds();
         }
-        if keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left) {
-            player.angle_rad -= AUTO_MOVE_AV / 1.0 * time.delta_seconds();
+        if actions.pressed(Action::SteerLeft) {
+            player.angle_rad -= game.difficulty.steering_rate * time.delta_seconds();
+        }
+
+        // The pointer or stick position is a target angle, the player turns towards it at the same rate as when steering with buttons
+        // Buttons take priority so the d-pad still works while the stick rests slightly off center
+        if let Some(target_x) = pointer_steering.x.or(stick_steering.x) {
+            if !actions.pressed(Action::SteerLeft) && !actions.pressed(Action::SteerRight) {
+                let target_angle = target_x * ANGLE_RANGE_RAD.max;
+                let max_turn = game.difficulty.steering_rate * time.delta_seconds();
+                player.angle_rad += (target_angle - player.angle_rad).clamp(-max_turn, max_turn);
+            }
         }
 
         // Do not let the angle exceed limits provided by ANGLE_RANGE_RAD
@@ -156,7 +229,87 @@ fn change_angle(
     }
 }
 
+// Work out where the pointer wants the player to head when using the pointer control scheme
+// A touch steers by how far it has been dragged, the mouse steers by how far it is from the plane
+fn update_pointer_steering(
+    settings: Res<settings::Settings>,
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    player_query: Query<&Transform, With<Player>>,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+    mut pointer_steering: ResMut<PointerSteering>,
+) {
+    pointer_steering.x = None;
+    if settings.control_scheme != input::ControlScheme::Pointer {
+        return;
+    }
+
+    if let (Ok((camera, camera_transform)), Ok(player_transform)) = (camera_query.get_single(), player_query.get_single()) {
+        let to_world_x = |position: Vec2| camera.viewport_to_world_2d(camera_transform, position).map(|world| world.x);
+
+        let offset = if let Some(touch) = touches.iter().next() {
+            to_world_x(touch.position()).zip(to_world_x(touch.start_position())).map(|(x, start_x)| x - start_x)
+        } else {
+            window_query.get_single().ok()
+                .and_then(|window| window.cursor_position())
+                .and_then(to_world_x)
+                .map(|x| x - player_transform.translation.x)
+        };
+
+        let full_turn_distance = art::PLAYER_SPRITE_SIZE.x * scale_factor.current * POINTER_FULL_TURN_PLAYER_WIDTHS;
+        pointer_steering.x = offset.map(|offset| (offset / full_turn_distance).clamp(-1.0, 1.0));
+    }
+}
+
+// Steer with a single button when using the one button control scheme
+// Each press turns the player away from the way they are facing
+// A tap carries on turning until the player faces the other way at ONE_BUTTON_TURN_FRACTION of the max angle
+// Holding the button keeps turning only while it is held, so the player can pick any angle
+fn one_button_steering(
+    actions: Res<Input<Action>>,
+    settings: Res<settings::Settings>,
+    mut player_query: Query<&mut Player>,
+    game: Res<game::Game>,
+    time: Res<Time>,
+) {
+    if settings.control_scheme != input::ControlScheme::OneButton {
+        return;
+    }
+
+    if let Ok(mut player) = player_query.get_single_mut() {
+        let max_turn = game.difficulty.steering_rate * time.delta_seconds();
+        let player = &mut *player;
+        let turn = &mut player.one_button_turn;
+
+        if actions.just_pressed(Action::OneButton) {
+            turn.direction = -player.facing.to_x();
+            turn.held_seconds = 0.0;
+            turn.target_rad = None;
+        }
+
+        if actions.pressed(Action::OneButton) {
+            turn.held_seconds += time.delta_seconds();
+            player.angle_rad += turn.direction * max_turn;
+        } else if actions.just_released(Action::OneButton) && turn.held_seconds < ONE_BUTTON_TAP_SECONDS {
+            turn.target_rad = Some(turn.direction * ANGLE_RANGE_RAD.max * ONE_BUTTON_TURN_FRACTION);
+        }
+
+        if let Some(target_rad) = turn.target_rad {
+            if (target_rad - player.angle_rad).abs() <= max_turn {
+                player.angle_rad = target_rad;
+                turn.target_rad = None;
+            } else {
+                player.angle_rad += (target_rad - player.angle_rad).signum() * max_turn;
+            }
+        }
+
+        player.angle_rad = player.angle_rad.clamp(ANGLE_RANGE_RAD.min, ANGLE_RANGE_RAD.max);
+    }
+}
+
 // Set players heading based on the players current rotation angle
+// The sprite only shows the angle, apart from the stepped flight model it has no effect on movement
 fn set_player_heading(
     mut player_query: Query<(&mut Player, &mut TextureAtlasSprite), Changed<Player>>,
 ) {
@@ -187,29 +340,63 @@ fn set_player_heading(
     }
 }
 
-// Calculate speed based on player spritesheet index
-fn calculate_speed(mut player_query: Query<(&mut Player, &TextureAtlasSprite)>, game: Res<game::Game>) {
+// Calculate speed using the selected flight model
+fn calculate_speed(
+    mut player_query: Query<(&mut Player, &TextureAtlasSprite)>,
+    game: Res<game::Game>,
+    settings: Res<settings::Settings>,
+    time: Res<Time>,
+) {
     if let Ok((player, sprite)) = &mut player_query.get_single_mut() {
+        let max_speed = game.difficulty.player_max_speed;
 
-        // Calculate how much the x and y speed changes each time the player spritesheet index is incremented / deincremented
-        let speed_steps = Vec2::new(
-            game.difficulty.player_max_speed.x / (art::PLAYER_SPRITESHEET_INDICES - 1) as f32,
-            game.difficulty.player_max_speed.y / art::PLAYER_SPRITESHEET_INDICES as f32
-        );
+        let target_speed = match settings.flight_model {
+            FlightModel::Stepped => stepped_speed(sprite.index, max_speed) * Vec2::new(player.facing.to_x(), 1.0),
+            FlightModel::Continuous | FlightModel::Inertia => continuous_speed(player.angle_rad, max_speed),
+        };
 
-        // Calculate speed
-        player.speed = Vec2::new(
-            speed_steps.x * generic::reverse_index(sprite.index, art::PLAYER_SPRITESHEET_INDICES) as f32, // As the sprite index increases the x speed should decrease (because at index 0 the plane is facing straight down)
-            speed_steps.y * (sprite.index + 1) as f32
-        )
+        if settings.flight_model == FlightModel::Inertia {
+            let max_change = max_speed * INERTIA_ACCELERATION * time.delta_seconds();
+            let speed_change = (target_speed - player.speed).clamp(-max_change, max_change);
+            player.speed += speed_change;
+        } else {
+            player.speed = target_speed;
+        }
     }
 }
 
+// Speed based on player spritesheet index
+fn stepped_speed(sprite_index: usize, max_speed: Vec2) -> Vec2 {
+
+    // Calculate how much the x and y speed changes each time the player spritesheet index is incremented / deincremented
+    let speed_steps = Vec2::new(
+        max_speed.x / (art::PLAYER_SPRITESHEET_INDICES - 1) as f32,
+        max_speed.y / art::PLAYER_SPRITESHEET_INDICES as f32
+    );
+
+    Vec2::new(
+        speed_steps.x * generic::reverse_index(sprite_index, art::PLAYER_SPRITESHEET_INDICES) as f32, // As the sprite index increases the x speed should decrease (because at index 0 the plane is facing straight down)
+        speed_steps.y * (sprite_index + 1) as f32
+    )
+}
+
+// Speed based on the players angle
+// Matches the stepped speed when the angle lines up with a sprite at either end of the spritesheet
+fn continuous_speed(angle_rad: f32, max_speed: Vec2) -> Vec2 {
+    let turn = (angle_rad.abs() / ANGLE_RANGE_RAD.max).min(1.0); // 0 when facing straight down, 1 when facing sideways
+    let min_y_fraction = 1.0 / art::PLAYER_SPRITESHEET_INDICES as f32;
+
+    Vec2::new(
+        max_speed.x * turn * angle_rad.signum(),
+        max_speed.y * (1.0 - turn * (1.0 - min_y_fraction)),
+    )
+}
+
 // Moves players x coordinate every frame according to player speed
 pub fn move_player(mut player_query: Query<(&mut Transform, &Player)>, time: Res<Time>) {
     if let Ok((mut transform, player)) = player_query.get_single_mut() {
     
-        transform.translation.x += player.speed.x * player.facing.to_x() * time.delta_seconds();
+        transform.translation.x += player.speed.x * time.delta_seconds();
         transform.translation.y -= player.speed.y * time.delta_seconds();
     }
 }
@@ -245,4 +432,19 @@ fn animate_death(
             
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continuous_speed_matches_stepped_at_the_ends() {
+        let max_speed = Vec2::new(100.0, 200.0);
+        let straight_down_index = art::PLAYER_SPRITESHEET_INDICES - 1;
+
+        assert!(continuous_speed(0.0, max_speed).abs_diff_eq(stepped_speed(straight_down_index, max_speed), 0.001));
+        assert!(continuous_speed(ANGLE_RANGE_RAD.max, max_speed).abs_diff_eq(stepped_speed(0, max_speed), 0.001));
+        assert_eq!(continuous_speed(ANGLE_RANGE_RAD.min, max_speed).x, -max_speed.x);
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use crate::{art, game, save, settings, AppState};
+use game::{platforms, player, difficulty, sprite_scaler, collisions, adaptive};
+
+// Statistics for the current run, shown on the game over screen
+#[derive(Resource, Default, Debug)]
+pub struct RunStats {
+    pub time_survived: f32, // Seconds, time spent paused isn't counted
+    pub depth: f32, // Distance flown down the corridor, in plane lengths
+    pub platforms_passed: u32, // Rows of platforms, a double sided pair counts once
+    pub near_misses: u32,
+    pub previous_best: i32, // Best score for the run mode before this run started
+    pub death: Option<collisions::Collision>, // What the player crashed into, None until the run ends
+
+    start_y: Option<f32>,
+}
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(RunStats::default())
+            .add_systems(OnEnter(AppState::GameSetup), reset_run_stats)
+            .add_systems(Update, (track_time_and_depth, count_platforms.after(platforms::pass_platforms), record_death.after(collisions::detect_collisions)).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)))
+
+            // Every run ends with a cleanup, whether the player crashed, restarted or went back to the main menu
+            .add_systems(OnEnter(AppState::GameCleanup), record_lifetime_stats);
+    }
+}
+
+impl RunStats {
+    pub fn beat_best(&self, score: i32) -> bool {
+        score > self.previous_best
+    }
+}
+
+fn reset_run_stats(
+    mut run_stats: ResMut<RunStats>,
+    save_data: Res<save::SaveData>,
+    preset: Res<difficulty::DifficultyPreset>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+    settings: Res<settings::Settings>,
+) {
+    *run_stats = RunStats {
+        previous_best: save_data.best_score(save::RunMode::current(*preset, &adaptive_difficulty, settings.control_scheme)),
+        ..default()
+    };
+}
+
+fn track_time_and_depth(
+    mut run_stats: ResMut<RunStats>,
+    player_query: Query<&Transform, With<player::Player>>,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+    time: Res<Time>,
+) {
+    run_stats.time_survived += time.delta_seconds();
+
+    if let Ok(player_transform) = player_query.get_single() {
+        let start_y = *run_stats.start_y.get_or_insert(player_transform.translation.y);
+        let plane_length = art::PLAYER_SPRITE_SIZE.y * scale_factor.current;
+        run_stats.depth = (start_y - player_transform.translation.y) / plane_length;
+    }
+}
+
+fn count_platforms(
+    mut run_stats: ResMut<RunStats>,
+    mut platform_passed: EventReader<platforms::PlatformPassed>,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+) {
+    for passed in platform_passed.read() {
+        run_stats.platforms_passed += 1;
+        if passed.is_near_miss(scale_factor.current) {
+            run_stats.near_misses += 1;
+        }
+    }
+}
+
+fn record_death(mut run_stats: ResMut<RunStats>, mut collisions: EventReader<collisions::Collision>) {
+    for collision in collisions.read() {
+        run_stats.death = Some(*collision);
+    }
+}
+
+fn record_lifetime_stats(run_stats: Res<RunStats>, game: Res<game::Game>, mut save_data: ResMut<save::SaveData>) {
+    save_data.lifetime.record_run(game.score, run_stats.time_survived, run_stats.platforms_passed, run_stats.death);
+    save_data.save();
+}
+
+// Seconds as minutes and seconds, such as 1:05
+pub fn time_text(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_text() {
+        assert_eq!(time_text(0.0), "0:00");
+        assert_eq!(time_text(65.9), "1:05");
+        assert_eq!(time_text(600.0), "10:00");
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::{art, data, game, AppState};
+use game::{camera, tiles};
+
+const THEMES_PATH: &str = "data/themes.ron";
+
+// Art used for a section of the level
+#[derive(Deserialize, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub wall_sprite: String,
+    pub platform_corner_sprite: String,
+    pub platform_straight_sprite: String,
+    pub background_sprite: String,
+    pub palette: Palette,
+}
+
+// Hex colours which tint each sprite of a theme
+#[derive(Deserialize, Clone)]
+pub struct Palette {
+    pub wall: String,
+    pub platform: String,
+    pub background: String,
+}
+
+// Every theme, loaded from THEMES_PATH
+#[derive(Resource, Deserialize)]
+pub struct Themes {
+    pub levels_per_theme: i32,
+    pub transition_rows: u32,
+    pub themes: Vec<Theme>,
+}
+
+// The theme new walls and platforms are spawned with
+#[derive(Resource, Default)]
+pub struct ActiveTheme {
+    pub index: usize,
+    previous_index: usize,
+    transition_rows_left: u32, // Wall rows left to spawn that fade from the previous theme
+}
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Themes::load())
+            .add_systems(OnEnter(AppState::GameSetup), insert_active_theme)
+            .add_systems(Update, switch_theme.after(game::recalculate_difficulty).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)))
+            .add_systems(Update, apply_background_theme.run_if(resource_exists_and_changed::<ActiveTheme>()));
+    }
+}
+
+impl Themes {
+    // Load themes from THEMES_PATH, or fall back to the default art when they can't be loaded
+    pub fn load() -> Self {
+        match data::load_ron::<Themes>(THEMES_PATH) {
+            Ok(themes) if !themes.themes.is_empty() => themes,
+            Ok(_) => {
+                warn!("{} doesn't contain any themes, using the default theme", THEMES_PATH);
+                Themes::default_theme()
+            },
+            Err(error) => {
+                warn!("{}, using the default theme", error);
+                Themes::default_theme()
+            },
+        }
+    }
+
+    // A single theme made from the art in art.rs
+    pub fn default_theme() -> Self {
+        Themes {
+            levels_per_theme: 1,
+            transition_rows: 0,
+            themes: vec![
+                Theme {
+                    name: "Paper".to_string(),
+                    wall_sprite: art::WALL_SPRITE_PATH.to_string(),
+                    platform_corner_sprite: art::PLATFORM_CORNER_SPRITE_PATH.to_string(),
+                    platform_straight_sprite: art::PLATFORM_STRAIGHT_SPRITE_PATH.to_string(),
+                    background_sprite: art::CAMERA_BACKGROUND_SRPITE.to_string(),
+                    palette: Palette {
+                        wall: "ffffff".to_string(),
+                        platform: "ffffff".to_string(),
+                        background: "ffffff".to_string(),
+                    },
+                },
+            ],
+        }
+    }
+
+    // Index of the theme used for a level
+    // Themes loop back to the first theme after the last one
+    pub fn theme_for_level(&self, level: i32) -> usize {
+        let theme = (level - 1).max(0) / self.levels_per_theme.max(1);
+        theme as usize % self.themes.len()
+    }
+}
+
+impl Palette {
+    pub fn wall_color(&self) -> Color {
+        palette_color(&self.wall)
+    }
+
+    pub fn platform_color(&self) -> Color {
+        palette_color(&self.platform)
+    }
+
+    pub fn background_color(&self) -> Color {
+        palette_color(&self.background)
+    }
+}
+
+// Parse a palette colour, invalid colours leave the sprite untinted
+fn palette_color(hex: &str) -> Color {
+    Color::hex(hex).unwrap_or_else(|_| {
+        warn!("Invalid theme palette colour {}", hex);
+        Color::WHITE
+    })
+}
+
+impl ActiveTheme {
+    // Material for the next wall row
+    // Rows in the transition band fade from the previous themes wall colour to the current themes wall colour
+    pub fn next_wall_material(
+        &mut self,
+        themes: &Themes,
+        tile_art: &tiles::TileArt,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Handle<ColorMaterial> {
+        let tile_set = &tile_art.sets[self.index];
+        if self.transition_rows_left == 0 {
+            return tile_set.wall_material.clone();
+        }
+
+        let progress = 1.0 - (self.transition_rows_left as f32 / (themes.transition_rows + 1) as f32);
+        let previous_color = themes.themes[self.previous_index].palette.wall_color().as_rgba_f32();
+        let current_color = themes.themes[self.index].palette.wall_color().as_rgba_f32();
+
+        let color: Vec<f32> = previous_color.iter().zip(current_color).map(|(previous, current)| previous + (current - previous) * progress).collect();
+
+        self.transition_rows_left -= 1;
+        materials.add(ColorMaterial {
+            color: Color::rgba(color[0], color[1], color[2], color[3]),
+            texture: Some(tile_set.wall_texture.clone()),
+        })
+    }
+}
+
+fn insert_active_theme(mut commands: Commands) {
+    commands.insert_resource(ActiveTheme::default());
+}
+
+// Move on to the next theme when the level changes
+fn switch_theme(
+    mut score_increase: EventReader<game::ScoreIncrease>,
+    game: Res<game::Game>,
+    themes: Res<Themes>,
+    mut active_theme: ResMut<ActiveTheme>,
+) {
+    for _ in score_increase.read() {
+        let index = themes.theme_for_level(game.difficulty.level);
+
+        if index != active_theme.index {
+            info!("Switching to the {} theme", themes.themes[index].name);
+            active_theme.previous_index = active_theme.index;
+            active_theme.index = index;
+            active_theme.transition_rows_left = themes.transition_rows;
+        }
+    }
+}
+
+// Change the camera background to match the active theme
+fn apply_background_theme(
+    active_theme: Res<ActiveTheme>,
+    themes: Res<Themes>,
+    tile_art: Res<tiles::TileArt>,
+    mut background_query: Query<(&mut Handle<Image>, &mut Sprite), With<camera::CameraBackground>>,
+) {
+    if let Ok((mut texture, mut sprite)) = background_query.get_single_mut() {
+        *texture = tile_art.sets[active_theme.index].background_texture.clone();
+        sprite.color = themes.themes[active_theme.index].palette.background_color();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themes_file_loads() {
+        let themes = data::load_ron::<Themes>(THEMES_PATH).unwrap();
+        assert!(!themes.themes.is_empty());
+
+        // Every theme has its own art, and all of it exists
+        let assets = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        for (index, theme) in themes.themes.iter().enumerate() {
+            for sprite in [&theme.wall_sprite, &theme.platform_corner_sprite, &theme.platform_straight_sprite, &theme.background_sprite] {
+                assert!(assets.join(sprite).exists(), "{} is missing", sprite);
+            }
+            assert!(themes.themes[..index].iter().all(|other| other.wall_sprite != theme.wall_sprite), "{} reuses another themes wall", theme.name);
+        }
+    }
+
+    #[test]
+    fn test_theme_for_level_loops() {
+        let mut themes = Themes::default_theme();
+        themes.levels_per_theme = 2;
+        themes.themes.push(themes.themes[0].clone());
+
+        assert_eq!(themes.theme_for_level(1), 0);
+        assert_eq!(themes.theme_for_level(3), 1);
+        assert_eq!(themes.theme_for_level(5), 0);
+    }
+}
@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+use bevy::render::render_resource::{Extent3d, PrimitiveTopology, TextureDimension, TextureFormat};
+use crate::{art, game};
+use game::theme;
+
+const BYTES_PER_PIXEL: usize = 4; // Sprites are loaded as rgba8 images
+
+// Handles used to draw platforms and walls
+// Loaded once, and shared between every platform and wall row so that each one can be drawn as a single mesh
+#[derive(Resource)]
+pub struct TileArt {
+    pub sets: Vec<TileSet>, // One set for every theme, in the same order as the themes
+}
+
+pub struct TileSet {
+    pub platform_material: Handle<ColorMaterial>,
+    pub wall_material: Handle<ColorMaterial>,
+    pub wall_texture: Handle<Image>,
+    pub background_texture: Handle<Image>,
+
+    platform_atlas: Handle<Image>, // Straight and corner platform tiles side by side, filled in once both sprites have loaded
+    platform_tiles: [Handle<Image>; 2], // [straight, corner]
+    platform_atlas_built: bool,
+}
+
+// Part of the platform atlas each platform tile uses, indexed by [straight, corner]
+pub const PLATFORM_TILE_UVS: [Rect; 2] = [
+    Rect {min: Vec2::new(0.0, 0.0), max: Vec2::new(0.5, 1.0)},
+    Rect {min: Vec2::new(0.5, 0.0), max: Vec2::new(1.0, 1.0)},
+];
+
+// Uv rect covering a whole texture
+pub const FULL_UV: Rect = Rect {min: Vec2::new(0.0, 0.0), max: Vec2::new(1.0, 1.0)};
+
+pub struct TilesPlugin;
+
+impl Plugin for TilesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, load_tile_art)
+            .add_systems(Update, build_platform_atlases);
+    }
+}
+
+// Builds a single mesh out of many square tiles
+#[derive(Default)]
+pub struct TileMeshBuilder {
+    positions: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl TileMeshBuilder {
+    // Adds a tile centered on location, rotated anticlockwise by rotation radians, showing the uv_rect part of the texture
+    pub fn push_tile(&mut self, location: Vec2, size: Vec2, rotation: f32, uv_rect: Rect) {
+        let rotation = Vec2::from_angle(rotation);
+        let half_size = size / 2.0;
+        let first_index = self.positions.len() as u32;
+
+        // Corners go anticlockwise from the top left, the top of the texture is the top of the tile
+        let corners = [
+            (Vec2::new(-half_size.x, half_size.y), Vec2::new(uv_rect.min.x, uv_rect.min.y)),
+            (Vec2::new(-half_size.x, -half_size.y), Vec2::new(uv_rect.min.x, uv_rect.max.y)),
+            (Vec2::new(half_size.x, -half_size.y), Vec2::new(uv_rect.max.x, uv_rect.max.y)),
+            (Vec2::new(half_size.x, half_size.y), Vec2::new(uv_rect.max.x, uv_rect.min.y)),
+        ];
+
+        for (corner, uv) in corners {
+            let position = location + rotation.rotate(corner);
+            self.positions.push([position.x, position.y, 0.0]);
+            self.uvs.push([uv.x, uv.y]);
+        }
+
+        self.indices.extend([0, 1, 2, 0, 2, 3].map(|i| first_index + i));
+    }
+
+    pub fn build(self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.set_indices(Some(Indices::U32(self.indices)));
+        mesh
+    }
+}
+
+// Load the tile sprites of every theme and create the materials they are drawn with
+pub fn load_tile_art(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    themes: Res<theme::Themes>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut sets = Vec::new();
+    for theme in themes.themes.iter() {
+
+        // The atlas is blank until the platform sprites are loaded
+        let platform_atlas = images.add(Image::new_fill(
+            Extent3d {
+                width: art::PLATFORM_SPRITE_SIZE.x as u32 * 2,
+                height: art::PLATFORM_SPRITE_SIZE.y as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; BYTES_PER_PIXEL],
+            TextureFormat::Rgba8UnormSrgb,
+        ));
+
+        let wall_texture: Handle<Image> = asset_server.load(&theme.wall_sprite);
+
+        sets.push(
+            TileSet {
+                platform_material: materials.add(ColorMaterial {
+                    color: theme.palette.platform_color(),
+                    texture: Some(platform_atlas.clone()),
+                }),
+                wall_material: materials.add(ColorMaterial {
+                    color: theme.palette.wall_color(),
+                    texture: Some(wall_texture.clone()),
+                }),
+                wall_texture,
+                background_texture: asset_server.load(&theme.background_sprite),
+
+                platform_atlas,
+                platform_tiles: [
+                    asset_server.load(&theme.platform_straight_sprite),
+                    asset_server.load(&theme.platform_corner_sprite),
+                ],
+                platform_atlas_built: false,
+            }
+        );
+    }
+
+    commands.insert_resource(TileArt {sets});
+}
+
+// Copy the straight and corner platform sprites into the platform atlas of each tile set once they have loaded
+fn build_platform_atlases(mut tile_art: ResMut<TileArt>, mut images: ResMut<Assets<Image>>) {
+    for tile_set in tile_art.sets.iter_mut() {
+        build_platform_atlas(tile_set, &mut images);
+    }
+}
+
+fn build_platform_atlas(tile_set: &mut TileSet, images: &mut Assets<Image>) {
+    if tile_set.platform_atlas_built {
+        return;
+    }
+
+    let tile_width = art::PLATFORM_SPRITE_SIZE.x as usize * BYTES_PER_PIXEL; // Bytes in one row of a tile
+    let tile_height = art::PLATFORM_SPRITE_SIZE.y as usize;
+
+    let mut atlas_data = vec![0; tile_width * tile_height * tile_set.platform_tiles.len()];
+    for (i, tile) in tile_set.platform_tiles.iter().enumerate() {
+        if let Some(tile_image) = images.get(tile) {
+            if tile_image.data.len() != tile_width * tile_height {
+                warn!("Platform tile {:?} isn't an rgba8 image with the size given in art.rs", tile.path());
+                tile_set.platform_atlas_built = true; // Don't try again, the atlas stays blank
+                return;
+            }
+
+            // Place each row of the tile next to the same row of the previous tile
+            for row in 0..tile_height {
+                let atlas_start = (row * tile_set.platform_tiles.len() + i) * tile_width;
+                atlas_data[atlas_start..atlas_start + tile_width].copy_from_slice(&tile_image.data[row * tile_width..(row + 1) * tile_width]);
+            }
+        } else {
+            return; // Wait for every tile to load
+        }
+    }
+
+    if let Some(atlas) = images.get_mut(&tile_set.platform_atlas) {
+        atlas.data = atlas_data;
+        tile_set.platform_atlas_built = true;
+    }
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use crate::{generic, input, locale, save, settings, AppState};
+use crate::game::{self, platforms, player};
+use input::{Action, ControlScheme, PointerSteering, StickSteering};
+
+const HOLD_SECONDS: f32 = 0.5; // How long a steering step has to be held for before moving on
+const STEERING_THRESHOLD: f32 = 0.3; // Steering input has to be pushed at least this far to count
+const COMPLETE_PROMPT_SECONDS: f32 = 3.0; // How long "Tutorial complete!" is shown for
+pub const TUTORIAL_SEED: u64 = 1; // Corridor seed of the tutorial, so the walls are the same every time
+
+// Steps of the tutorial, in order apart from the two turns, see TutorialStep::first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    TurnRight,
+    TurnLeft,
+    PassPlatform,
+    Complete,
+}
+
+// Tutorial shown on the first run, and whenever it is picked from the main menu
+// The layout is scripted while it is active: a fixed corridor, with wall platforms alternating sides at a fixed gap
+// The run carries on as a normal run once the tutorial is complete
+#[derive(Resource, Default, Debug)]
+pub struct Tutorial {
+    pub replay: bool, // Set by the main menu to play the tutorial again after it has been completed, cleared when the run ends
+    pub step: Option<TutorialStep>, // None when the run isn't a tutorial, or the tutorial is over
+    pub tutorial_run: bool, // True for the whole of a run which started with the tutorial, its score isn't recorded
+
+    control_scheme: ControlScheme, // Scheme the tutorial was started with, it decides which turn comes first
+    hold_seconds: f32,
+    complete_seconds: f32,
+}
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Tutorial::default())
+            .add_systems(OnEnter(AppState::GameSetup), start_tutorial.before(game::insert_game_resouorces))
+            .add_systems(OnEnter(AppState::GameCleanup), clear_replay)
+            .add_systems(Update, advance_tutorial.after(platforms::pass_platforms).run_if(in_state(AppState::Game)).run_if(in_state(game::GameState::Running)));
+    }
+}
+
+impl TutorialStep {
+    // A one button press turns away from the way the plane is heading, so one button starts with the turn away from the start direction
+    pub fn first(control_scheme: ControlScheme) -> Self {
+        match (control_scheme, player::START_DIRECTION) {
+            (ControlScheme::OneButton, generic::Direction::Right) => TutorialStep::TurnLeft,
+            _ => TutorialStep::TurnRight,
+        }
+    }
+
+    // The first turn is followed by the other turn, and then passing a platform
+    pub fn next(&self, control_scheme: ControlScheme) -> Self {
+        match self {
+            TutorialStep::TurnRight | TutorialStep::TurnLeft if *self != TutorialStep::first(control_scheme) => TutorialStep::PassPlatform,
+            TutorialStep::TurnRight => TutorialStep::TurnLeft,
+            TutorialStep::TurnLeft => TutorialStep::TurnRight,
+            TutorialStep::PassPlatform | TutorialStep::Complete => TutorialStep::Complete,
+        }
+    }
+}
+
+impl Tutorial {
+    // True while the tutorial is still teaching, the difficulty stays gentle until then
+    pub fn active(&self) -> bool {
+        matches!(self.step, Some(step) if step != TutorialStep::Complete)
+    }
+
+    // Move on to the next step
+    fn advance(&mut self) {
+        if let Some(step) = self.step {
+            self.step = Some(step.next(self.control_scheme));
+            self.hold_seconds = 0.0;
+        }
+    }
+
+    // Prompt for the current step, empty when there is nothing to show
+    pub fn prompt(&self, settings: &settings::Settings, locale: &locale::Locale) -> String {
+        let binding_name = |action: Action| settings.bindings.get(action).first().map(|binding| binding.name()).unwrap_or_default();
+
+        match (self.step, settings.control_scheme) {
+            (None, _) => String::new(),
+            (Some(TutorialStep::TurnRight), ControlScheme::Buttons) => locale.format("Hold {} to turn right", &[&binding_name(Action::SteerRight)]),
+            (Some(TutorialStep::TurnLeft), ControlScheme::Buttons) => locale.format("Hold {} to turn left", &[&binding_name(Action::SteerLeft)]),
+            (Some(TutorialStep::TurnRight), ControlScheme::Pointer) => locale.get("Move the pointer right of the plane to turn right"),
+            (Some(TutorialStep::TurnLeft), ControlScheme::Pointer) => locale.get("Move the pointer left of the plane to turn left"),
+            (Some(TutorialStep::TurnRight), ControlScheme::OneButton) => locale.format("Hold {} to turn right", &[&binding_name(Action::OneButton)]),
+            (Some(TutorialStep::TurnLeft), ControlScheme::OneButton) => locale.format("Hold {} to turn left", &[&binding_name(Action::OneButton)]),
+            (Some(TutorialStep::PassPlatform), _) => locale.get("Fly under a platform to score"),
+            (Some(TutorialStep::Complete), _) => locale.get("Tutorial complete!"),
+        }
+    }
+}
+
+// Start the tutorial on the first run, or when it was picked from the main menu
+fn start_tutorial(mut tutorial: ResMut<Tutorial>, save_data: Res<save::SaveData>, settings: Res<settings::Settings>) {
+    let step = if tutorial.replay || !save_data.tutorial_complete {
+        Some(TutorialStep::first(settings.control_scheme))
+    } else {
+        None
+    };
+
+    *tutorial = Tutorial {
+        replay: tutorial.replay,
+        step,
+        tutorial_run: step.is_some(),
+        control_scheme: settings.control_scheme,
+        ..default()
+    };
+}
+
+// Only the run started from the tutorial button replays it, even if the player crashed or quit before finishing
+fn clear_replay(mut tutorial: ResMut<Tutorial>) {
+    tutorial.replay = false;
+}
+
+// Move through the steps as the player does what each prompt asks
+#[allow(clippy::too_many_arguments)]
+fn advance_tutorial(
+    mut tutorial: ResMut<Tutorial>,
+    mut save_data: ResMut<save::SaveData>,
+    mut platform_passed: EventReader<platforms::PlatformPassed>,
+    actions: Res<Input<Action>>,
+    stick_steering: Res<StickSteering>,
+    pointer_steering: Res<PointerSteering>,
+    player_query: Query<&player::Player>,
+    settings: Res<settings::Settings>,
+    time: Res<Time>,
+) {
+    let passed_platform = platform_passed.read().count() > 0;
+
+    let step = match tutorial.step {
+        Some(step) => step,
+        None => return,
+    };
+
+    // -1 (left) to 1 (right)
+    // With one button the direction is the way the held button is turning the plane
+    let steering = match settings.control_scheme {
+        ControlScheme::Buttons if actions.pressed(Action::SteerRight) => 1.0,
+        ControlScheme::Buttons if actions.pressed(Action::SteerLeft) => -1.0,
+        ControlScheme::Buttons => stick_steering.x.unwrap_or(0.0),
+        ControlScheme::Pointer => pointer_steering.x.unwrap_or(0.0),
+        ControlScheme::OneButton if actions.pressed(Action::OneButton) => {
+            player_query.get_single().map(|player| player.one_button_turn_direction()).unwrap_or(0.0)
+        },
+        ControlScheme::OneButton => 0.0,
+    };
+
+    match step {
+        TutorialStep::TurnRight | TutorialStep::TurnLeft => {
+            let held = match step {
+                TutorialStep::TurnRight => steering > STEERING_THRESHOLD,
+                _ => steering < -STEERING_THRESHOLD,
+            };
+
+            if held {
+                tutorial.hold_seconds += time.delta_seconds();
+            }
+            if tutorial.hold_seconds >= HOLD_SECONDS {
+                tutorial.advance();
+            }
+        },
+        TutorialStep::PassPlatform => {
+            if passed_platform {
+                tutorial.advance();
+
+                save_data.tutorial_complete = true;
+                save_data.save();
+            }
+        },
+        TutorialStep::Complete => {
+            tutorial.complete_seconds += time.delta_seconds();
+            if tutorial.complete_seconds >= COMPLETE_PROMPT_SECONDS {
+                tutorial.step = None;
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_end_at_complete() {
+        let mut tutorial = Tutorial {step: Some(TutorialStep::TurnRight), hold_seconds: HOLD_SECONDS, ..default()};
+        assert!(tutorial.active());
+
+        tutorial.advance();
+        assert_eq!(tutorial.step, Some(TutorialStep::TurnLeft));
+        assert_eq!(tutorial.hold_seconds, 0.0);
+
+        tutorial.advance();
+        tutorial.advance();
+        tutorial.advance();
+        assert_eq!(tutorial.step, Some(TutorialStep::Complete));
+        assert!(!tutorial.active());
+    }
+
+    #[test]
+    fn test_one_button_turns_both_ways() {
+        let mut tutorial = Tutorial {step: Some(TutorialStep::first(ControlScheme::OneButton)), control_scheme: ControlScheme::OneButton, ..default()};
+        assert_eq!(tutorial.step, Some(TutorialStep::TurnLeft));
+
+        tutorial.advance();
+        assert_eq!(tutorial.step, Some(TutorialStep::TurnRight));
+
+        tutorial.advance();
+        assert_eq!(tutorial.step, Some(TutorialStep::PassPlatform));
+    }
+}
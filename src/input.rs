@@ -0,0 +1,355 @@
+use bevy::prelude::*;
+use bevy::input::InputSystem;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::settings;
+
+const STICK_DEADZONE: f32 = 0.15; // Stick positions closer to the center than this are ignored, so stick drift doesn't steer
+
+// Things the player can do
+// Systems read actions through Res<Input<Action>> instead of reading keys, so the keys can be rebound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    SteerLeft,
+    SteerRight,
+    Pause,
+    ToggleFullscreen,
+    Restart,
+    ToggleDebug,
+    MenuUp,
+    MenuDown,
+    MenuSelect,
+    OneButton,
+}
+
+// An input which triggers an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    GamepadButton(GamepadButtonType), // The button on any connected gamepad
+}
+
+// How the player steers the plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ControlScheme {
+    #[default]
+    Buttons, // Steering actions, and the gamepad stick
+    Pointer, // Mouse position relative to the plane, or dragging on a touchscreen
+    OneButton, // The one button action turns the plane the other way, see player::one_button_steering
+}
+
+// Where the pointer wants the plane to head, see player::update_pointer_steering
+// None when the pointer isn't steering
+#[derive(Resource, Default, Debug)]
+pub struct PointerSteering {
+    pub x: Option<f32>, // -1 (left) to 1 (right)
+}
+
+// Set while the player is typing, such as entering a name for the high score table
+// Key bindings are ignored while typing, so typed letters don't trigger actions
+#[derive(Resource, Default, Debug)]
+pub struct TextEntry {
+    pub active: bool,
+    held_keys: Vec<KeyCode>, // Keys pressed while typing, ignored until released so the key which ends typing doesn't also trigger an action
+}
+
+// Left stick x position of a gamepad, used for analog steering
+// None when no stick is being pushed
+#[derive(Resource, Default, Debug)]
+pub struct StickSteering {
+    pub x: Option<f32>, // -1 (left) to 1 (right)
+}
+
+// Every binding for each action, stored in the settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputBindings(pub HashMap<Action, Vec<Binding>>);
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Input::<Action>::default())
+            .insert_resource(StickSteering::default())
+            .insert_resource(PointerSteering::default())
+            .insert_resource(TextEntry::default())
+            .add_systems(PreUpdate, update_actions.after(InputSystem));
+    }
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::SteerLeft,
+        Action::SteerRight,
+        Action::Pause,
+        Action::ToggleFullscreen,
+        Action::Restart,
+        Action::ToggleDebug,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuSelect,
+        Action::OneButton,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::SteerLeft => "Steer left",
+            Action::SteerRight => "Steer right",
+            Action::Pause => "Pause",
+            Action::ToggleFullscreen => "Fullscreen",
+            Action::Restart => "Restart",
+            Action::ToggleDebug => "Debug overlay",
+            Action::MenuUp => "Menu up",
+            Action::MenuDown => "Menu down",
+            Action::MenuSelect => "Menu select",
+            Action::OneButton => "One button turn",
+        }
+    }
+
+    // Bindings used when the settings don't have any for an action
+    pub fn default_bindings(&self) -> Vec<Binding> {
+        match self {
+            Action::SteerLeft => vec![
+                Binding::Key(KeyCode::A),
+                Binding::Key(KeyCode::Left),
+                Binding::GamepadButton(GamepadButtonType::DPadLeft),
+                Binding::GamepadButton(GamepadButtonType::LeftTrigger),
+            ],
+            Action::SteerRight => vec![
+                Binding::Key(KeyCode::D),
+                Binding::Key(KeyCode::Right),
+                Binding::GamepadButton(GamepadButtonType::DPadRight),
+                Binding::GamepadButton(GamepadButtonType::RightTrigger),
+            ],
+            Action::Pause => vec![Binding::Key(KeyCode::Escape), Binding::GamepadButton(GamepadButtonType::Start)],
+            Action::ToggleFullscreen => vec![Binding::Key(KeyCode::F11)],
+            Action::Restart => vec![Binding::Key(KeyCode::R), Binding::GamepadButton(GamepadButtonType::Select)],
+            Action::ToggleDebug => vec![Binding::Key(KeyCode::F3)],
+            Action::MenuUp => vec![
+                Binding::Key(KeyCode::W),
+                Binding::Key(KeyCode::Up),
+                Binding::GamepadButton(GamepadButtonType::DPadUp),
+            ],
+            Action::MenuDown => vec![
+                Binding::Key(KeyCode::S),
+                Binding::Key(KeyCode::Down),
+                Binding::GamepadButton(GamepadButtonType::DPadDown),
+            ],
+            Action::MenuSelect => vec![Binding::Key(KeyCode::Return), Binding::GamepadButton(GamepadButtonType::South)],
+            Action::OneButton => vec![Binding::Key(KeyCode::Space), Binding::GamepadButton(GamepadButtonType::RightTrigger2)],
+        }
+    }
+}
+
+impl ControlScheme {
+    pub fn next(&self) -> Self {
+        match self {
+            ControlScheme::Buttons => ControlScheme::Pointer,
+            ControlScheme::Pointer => ControlScheme::OneButton,
+            ControlScheme::OneButton => ControlScheme::Buttons,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlScheme::Buttons => "Buttons",
+            ControlScheme::Pointer => "Pointer",
+            ControlScheme::OneButton => "One button",
+        }
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        InputBindings(Action::ALL.iter().map(|action| (*action, action.default_bindings())).collect())
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(|bindings| bindings.as_slice()).unwrap_or(&[])
+    }
+
+    // Action which already uses a binding, other than the given action
+    pub fn conflict(&self, binding: Binding, action: Action) -> Option<Action> {
+        Action::ALL.into_iter().find(|other| *other != action && self.get(*other).contains(&binding))
+    }
+
+    // Replace an actions bindings for the same device as the new binding
+    // Rebinding a key keeps the gamepad bindings, and rebinding a gamepad button keeps the keys
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|existing| !existing.same_device(&binding));
+        bindings.push(binding);
+    }
+
+    // Give actions the default bindings for any device they have no bindings for
+    // Lets settings saved before an action existed, or before it had keyboard defaults, keep working
+    // Defaults already used by another action are skipped, so a rebinding is never taken over
+    pub fn fill_missing_defaults(&mut self) {
+        for action in Action::ALL {
+            let bindings = self.get(action);
+            let missing: Vec<Binding> = action.default_bindings().into_iter()
+                .filter(|default| !bindings.iter().any(|binding| binding.same_device(default)))
+                .filter(|default| self.conflict(*default, action).is_none())
+                .collect();
+
+            self.0.entry(action).or_default().extend(missing);
+        }
+    }
+}
+
+impl Binding {
+    pub fn name(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{:?}", key),
+            Binding::GamepadButton(button_type) => format!("Pad {:?}", button_type),
+        }
+    }
+
+    fn same_device(&self, other: &Binding) -> bool {
+        matches!((self, other), (Binding::Key(_), Binding::Key(_)) | (Binding::GamepadButton(_), Binding::GamepadButton(_)))
+    }
+
+    fn pressed(&self, keyboard_input: &Input<KeyCode>, gamepads: &Gamepads, gamepad_buttons: &Input<GamepadButton>) -> bool {
+        match self {
+            Binding::Key(key) => keyboard_input.pressed(*key),
+            Binding::GamepadButton(button_type) => gamepads.iter().any(|gamepad| gamepad_buttons.pressed(GamepadButton::new(gamepad, *button_type))),
+        }
+    }
+}
+
+// Press and release actions based on the state of their bindings, and read the steering stick
+#[allow(clippy::too_many_arguments)]
+pub fn update_actions(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    settings: Res<settings::Settings>,
+    mut text_entry: ResMut<TextEntry>,
+    mut actions: ResMut<Input<Action>>,
+    mut stick_steering: ResMut<StickSteering>,
+) {
+    actions.clear();
+
+    if text_entry.active {
+        text_entry.held_keys = keyboard_input.get_pressed().copied().collect();
+    } else {
+        text_entry.held_keys.retain(|key| keyboard_input.pressed(*key));
+    }
+
+    for action in Action::ALL {
+        let pressed = settings.bindings.get(action).iter()
+            .filter(|binding| !matches!(binding, Binding::Key(key) if text_entry.active || text_entry.held_keys.contains(key)))
+            .any(|binding| binding.pressed(&keyboard_input, &gamepads, &gamepad_buttons));
+
+        if pressed {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+
+    // Use the first stick that is pushed past the deadzone
+    stick_steering.x = gamepads.iter()
+        .filter_map(|gamepad| gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)))
+        .find(|x| x.abs() > STICK_DEADZONE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::input::keyboard::KeyboardInput;
+    use bevy::input::gamepad::{GamepadEvent, GamepadConnection, GamepadConnectionEvent, GamepadInfo, GamepadButtonChangedEvent, GamepadAxisChangedEvent};
+
+    fn input_app() -> App {
+        let mut app = App::new();
+        app
+            .add_plugins((MinimalPlugins, InputPlugin, ActionPlugin))
+            .insert_resource(settings::Settings::default());
+
+        app.world.send_event(GamepadEvent::Connection(GamepadConnectionEvent::new(
+            Gamepad::new(0),
+            GamepadConnection::Connected(GamepadInfo {name: "Test pad".to_string()}),
+        )));
+        app.update();
+        app
+    }
+
+    #[test]
+    fn test_gamepad_button_presses_action() {
+        let mut app = input_app();
+
+        app.world.send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(Gamepad::new(0), GamepadButtonType::DPadLeft, 1.0)));
+        app.update();
+
+        let actions = app.world.resource::<Input<Action>>();
+        assert!(actions.just_pressed(Action::SteerLeft));
+        assert!(!actions.pressed(Action::SteerRight));
+    }
+
+    #[test]
+    fn test_key_held_when_typing_ends_is_ignored_until_released() {
+        let mut app = input_app();
+        let send_key = |app: &mut App, state: ButtonState| {
+            app.world.send_event(KeyboardInput {scan_code: 0, key_code: Some(KeyCode::Return), state, window: Entity::PLACEHOLDER});
+            app.update();
+        };
+
+        app.world.resource_mut::<TextEntry>().active = true;
+        send_key(&mut app, ButtonState::Pressed);
+        assert!(!app.world.resource::<Input<Action>>().pressed(Action::MenuSelect));
+
+        // Typing ends while Return is still held, such as when Return submits a name
+        app.world.resource_mut::<TextEntry>().active = false;
+        app.update();
+        assert!(!app.world.resource::<Input<Action>>().pressed(Action::MenuSelect));
+
+        send_key(&mut app, ButtonState::Released);
+        send_key(&mut app, ButtonState::Pressed);
+        assert!(app.world.resource::<Input<Action>>().just_pressed(Action::MenuSelect));
+    }
+
+    #[test]
+    fn test_rebind_keeps_other_device() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(Action::SteerLeft, Binding::Key(KeyCode::J));
+
+        assert_eq!(bindings.get(Action::SteerLeft)[0], Binding::GamepadButton(GamepadButtonType::DPadLeft));
+        assert!(bindings.get(Action::SteerLeft).contains(&Binding::Key(KeyCode::J)));
+        assert!(!bindings.get(Action::SteerLeft).contains(&Binding::Key(KeyCode::A)));
+
+        assert_eq!(bindings.conflict(Binding::Key(KeyCode::J), Action::SteerRight), Some(Action::SteerLeft));
+        assert_eq!(bindings.conflict(Binding::Key(KeyCode::J), Action::SteerLeft), None);
+    }
+
+    #[test]
+    fn test_fill_missing_defaults_adds_keys_to_gamepad_only_actions() {
+        let mut bindings = InputBindings::default();
+        bindings.0.insert(Action::MenuSelect, vec![Binding::GamepadButton(GamepadButtonType::South)]);
+        bindings.0.remove(&Action::MenuUp);
+        bindings.rebind(Action::ToggleDebug, Binding::Key(KeyCode::S)); // Takes one of the MenuDown defaults
+        bindings.0.insert(Action::MenuDown, vec![Binding::GamepadButton(GamepadButtonType::DPadDown)]);
+        bindings.fill_missing_defaults();
+
+        assert_eq!(bindings.get(Action::MenuSelect), [Binding::GamepadButton(GamepadButtonType::South), Binding::Key(KeyCode::Return)]);
+        assert_eq!(bindings.get(Action::MenuUp), Action::MenuUp.default_bindings().as_slice());
+        assert!(bindings.get(Action::MenuDown).contains(&Binding::Key(KeyCode::Down)));
+        assert!(!bindings.get(Action::MenuDown).contains(&Binding::Key(KeyCode::S)));
+    }
+
+    #[test]
+    fn test_stick_steering_ignores_deadzone() {
+        let mut app = input_app();
+
+        app.world.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(Gamepad::new(0), GamepadAxisType::LeftStickX, STICK_DEADZONE / 2.0)));
+        app.update();
+        assert_eq!(app.world.resource::<StickSteering>().x, None);
+
+        app.world.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(Gamepad::new(0), GamepadAxisType::LeftStickX, -0.8)));
+        app.update();
+        assert!((app.world.resource::<StickSteering>().x.unwrap() + 0.8).abs() < 0.01);
+    }
+}
@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use bevy::text::Font as FontAsset;
+use ab_glyph::Font;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use crate::{data, settings};
+
+pub const FALLBACK_FONT_PATH: &str = "fonts/DejaVuSans.ttf"; // Used for text with glyphs missing from the menu font
+
+// Languages the ui can be shown in
+// Strings are looked up by their English text, so English has no string table and missing translations fall back to English
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+// Translated strings for the current language
+#[derive(Resource, Default, Debug)]
+pub struct Locale {
+    pub language: Language,
+    strings: HashMap<String, String>,
+}
+
+// Text which is translated again when the language changes
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+#[derive(Resource)]
+struct FallbackFont(Handle<FontAsset>);
+
+// Fonts of each section of text from before any were switched to the fallback font
+#[derive(Component)]
+struct OriginalFonts(Vec<Handle<FontAsset>>);
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, load_fallback_font)
+            .add_systems(Update, (
+                change_language.run_if(resource_changed::<settings::Settings>()),
+                update_localized_texts.run_if(resource_changed::<Locale>()),
+                apply_fallback_font,
+            ).chain());
+    }
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::English, Language::French, Language::German, Language::Spanish];
+
+    pub fn next(&self) -> Self {
+        let index = Language::ALL.iter().position(|language| language == self).unwrap_or(0);
+        Language::ALL[(index + 1) % Language::ALL.len()]
+    }
+
+    // Name of the language in that language, so it can be found when the ui is in a language the player can't read
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+    }
+
+    // Path of the string table in the assets folder, English is the source language so has none
+    fn path(&self) -> Option<&'static str> {
+        match self {
+            Language::English => None,
+            Language::French => Some("lang/fr.ron"),
+            Language::German => Some("lang/de.ron"),
+            Language::Spanish => Some("lang/es.ron"),
+        }
+    }
+}
+
+impl Locale {
+    // Load the string table for a language, falling back to English when it can't be loaded
+    pub fn load(language: Language) -> Self {
+        let strings = match language.path().map(data::load_ron::<HashMap<String, String>>) {
+            Some(Ok(strings)) => strings,
+            Some(Err(error)) => {
+                warn!("{}, using English", error);
+                HashMap::new()
+            },
+            None => HashMap::new(),
+        };
+
+        Locale {language, strings}
+    }
+
+    // Translation of an English string, or the English string itself when there is no translation
+    pub fn get(&self, english: &str) -> String {
+        self.strings.get(english).cloned().unwrap_or_else(|| english.to_string())
+    }
+
+    // Translate a string containing {} placeholders, then fill them in order with the arguments
+    pub fn format(&self, english: &str, arguments: &[&dyn Display]) -> String {
+        let translated = self.get(english);
+        let mut pieces = translated.split("{}");
+        let mut formatted = pieces.next().unwrap_or_default().to_string();
+
+        for (index, piece) in pieces.enumerate() {
+            if let Some(argument) = arguments.get(index) {
+                formatted.push_str(&argument.to_string());
+            }
+            formatted.push_str(piece);
+        }
+
+        formatted
+    }
+}
+
+fn load_fallback_font(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(FallbackFont(asset_server.load(FALLBACK_FONT_PATH)));
+}
+
+fn change_language(settings: Res<settings::Settings>, mut locale: ResMut<Locale>) {
+    if settings.language != locale.language {
+        *locale = Locale::load(settings.language);
+    }
+}
+
+fn update_localized_texts(locale: Res<Locale>, mut text_query: Query<(&mut Text, &LocalizedText)>) {
+    for (mut text, localized_text) in text_query.iter_mut() {
+        text.sections[0].value = locale.get(localized_text.0);
+    }
+}
+
+// Switch text to the fallback font when its font doesn't have a glyph for every character, such as accented letters
+// Switched text keeps its original fonts, and goes back to them once they have every glyph again, such as after changing back to English
+// Text is checked when it changes, and all text is checked again when a font finishes loading
+fn apply_fallback_font(
+    mut commands: Commands,
+    mut text_query: Query<(Entity, &mut Text, Option<&OriginalFonts>)>,
+    mut font_events: EventReader<AssetEvent<FontAsset>>,
+    fonts: Res<Assets<FontAsset>>,
+    fallback_font: Res<FallbackFont>,
+) {
+    let font_loaded = font_events.read().count() > 0;
+
+    for (entity, mut text, original_fonts) in text_query.iter_mut() {
+        if !font_loaded && !text.is_changed() {
+            continue;
+        }
+
+        let original_fonts = match original_fonts {
+            Some(original_fonts) => original_fonts.0.clone(),
+            None => text.sections.iter().map(|section| section.style.font.clone()).collect(),
+        };
+
+        // Font each section should use, None while its original font is still loading
+        let wanted_fonts: Vec<Option<Handle<FontAsset>>> = text.sections.iter().enumerate()
+            .map(|(index, section)| {
+                let original_font = original_fonts.get(index).unwrap_or(&section.style.font);
+                fonts.get(original_font).map(|font| if has_glyphs(font, &section.value) {
+                    original_font.clone()
+                } else {
+                    fallback_font.0.clone()
+                })
+            })
+            .collect();
+
+        // Only change the text when a font differs, so it isn't marked as changed every frame
+        let fonts_differ = text.sections.iter().zip(&wanted_fonts)
+            .any(|(section, wanted_font)| wanted_font.as_ref().is_some_and(|font| *font != section.style.font));
+
+        if fonts_differ {
+            for (section, wanted_font) in text.sections.iter_mut().zip(wanted_fonts) {
+                if let Some(font) = wanted_font {
+                    section.style.font = font;
+                }
+            }
+
+            commands.entity(entity).insert(OriginalFonts(original_fonts));
+        }
+    }
+}
+
+fn has_glyphs(font: &FontAsset, text: &str) -> bool {
+    text.chars().filter(|character| !character.is_whitespace()).all(|character| font.font.glyph_id(character).0 != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_files_load() {
+        for language in Language::ALL {
+            if let Some(path) = language.path() {
+                data::load_ron::<HashMap<String, String>>(path).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_translation_falls_back_to_english() {
+        let locale = Locale {
+            language: Language::French,
+            strings: HashMap::from([("Level {}".to_string(), "Niveau {}".to_string())]),
+        };
+
+        assert_eq!(locale.get("Play"), "Play");
+        assert_eq!(locale.format("Level {}", &[&3]), "Niveau 3");
+        assert_eq!(locale.format("{} best: {}", &[&"Hard", &12]), "Hard best: 12");
+    }
+}
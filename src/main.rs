@@ -1,11 +1,15 @@
 use bevy::prelude::*;
-use bevy::window::WindowMode;
 
 pub mod game;
 pub mod art;
 pub mod generic;
 pub mod screen_mode;
 pub mod cursor;
+pub mod data;
+pub mod save;
+pub mod settings;
+pub mod input;
+pub mod locale;
 
 
 pub mod ui;
@@ -27,22 +31,24 @@ pub struct GameCleanupEvent {
 }
 
 fn main() {
+    let settings = settings::Settings::load(); // Loaded before the app is built, so the window is created with the saved settings
+
     App::new()
         .add_event::<GameCleanupEvent>()
         .add_state::<AppState>()
 
         .add_plugins((
-            DefaultPlugins.set(WindowPlugin { // Change window plugin to default to bordless fullscreen
-                primary_window: Some(Window {
-                    mode: WindowMode::BorderlessFullscreen,
-                    ..default()
-                }),
+            DefaultPlugins.set(WindowPlugin { // Create the window using the window settings
+                primary_window: Some(settings.window()),
                 ..default()
             })
             .set(ImagePlugin::default_nearest()), // Change ImagePlugin to render sprites with nearest scaling
         ))
 
-        .add_plugins((game::GamePlugin, generic::GenericPlugin, ui::UiPlugin, screen_mode::ScreenModePlugin, cursor::AutoHideCursorPlugin))
+        .insert_resource(locale::Locale::load(settings.language))
+        .insert_resource(ui::theme::UiTheme::load(settings.ui_palette))
+        .insert_resource(settings)
+        .add_plugins((game::GamePlugin, generic::GenericPlugin, ui::UiPlugin, screen_mode::ScreenModePlugin, cursor::AutoHideCursorPlugin, save::SavePlugin, settings::SettingsPlugin, input::ActionPlugin, locale::LocalePlugin))
 
         .add_systems(OnEnter(AppState::GameCleanup), game_cleanup_transition)
         .add_systems(OnEnter(AppState::GameSetup), game_setup_transition)
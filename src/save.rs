@@ -0,0 +1,326 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{data, game, generic, input, locale, settings};
+use game::{adaptive, difficulty};
+use game::difficulty::DifficultyPreset;
+use game::collisions::{Collision, Obstacle};
+
+const SAVE_FILE_NAME: &str = "save.ron";
+pub const HIGH_SCORE_TABLE_SIZE: usize = 10;
+
+// Progress kept between runs of the game
+// Stored in the users data directory
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct SaveData {
+    pub mode_best_scores: HashMap<RunMode, i32>, // Each run mode has its own best score, so they can be compared fairly
+    pub high_scores: Vec<HighScore>, // Best runs across every preset, highest score first
+    pub tutorial_complete: bool,
+    pub lifetime: LifetimeStats,
+
+    // Best scores saved before they were split by run mode, moved into mode_best_scores when loading
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    best_scores: HashMap<DifficultyPreset, i32>,
+}
+
+// How a run was played, scores are only compared between runs played the same way
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RunMode {
+    pub preset: DifficultyPreset,
+    pub adaptive: bool, // Adaptive difficulty was on
+    pub assisted: bool, // Played with a control scheme which makes the game easier, see difficulty::control_scheme_multipliers
+}
+
+// Totals across every run, shown on the statistics screen
+// Best scores for each run mode are kept in SaveData::mode_best_scores
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub total_runs: u32,
+    pub total_platforms_passed: u32,
+    pub total_play_time: f32, // Seconds
+    pub total_score: i64, // Used for the average score
+    pub longest_run: f32, // Seconds survived in the longest run
+    pub deaths: DeathCounts,
+}
+
+// How many runs ended by crashing into each obstacle, on each side of the level
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct DeathCounts {
+    pub wall_left: u32,
+    pub wall_right: u32,
+    pub platform_left: u32,
+    pub platform_right: u32,
+}
+
+// A run in the high score table
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HighScore {
+    pub name: String,
+    pub score: i32,
+    pub date: String, // YYYY-MM-DD
+    pub preset: DifficultyPreset,
+    pub seed: u64, // Corridor seed of the run
+    pub level: i32, // Level reached
+    #[serde(default)]
+    pub adaptive: bool, // Adaptive difficulty was on
+    #[serde(default)]
+    pub control_scheme: input::ControlScheme,
+}
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(SaveData::load())
+            .add_systems(OnEnter(game::GameState::GameOver), record_best_score);
+    }
+}
+
+impl SaveData {
+    // Load save data, or start with empty save data when there is none
+    pub fn load() -> Self {
+        let mut save_data: SaveData = save_path().and_then(|path| data::read_user_ron(&path)).unwrap_or_default();
+        save_data.migrate_best_scores();
+        save_data
+    }
+
+    // Best scores from before run modes were kept as the best scores of plain runs
+    fn migrate_best_scores(&mut self) {
+        for (preset, score) in self.best_scores.drain() {
+            let best = self.mode_best_scores.entry(RunMode::plain(preset)).or_insert(0);
+            *best = (*best).max(score);
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = save_path() {
+            data::write_user_ron(&path, self);
+        } else {
+            warn!("Couldn't find a data directory to save to");
+        }
+    }
+
+    pub fn best_score(&self, mode: RunMode) -> i32 {
+        *self.mode_best_scores.get(&mode).unwrap_or(&0)
+    }
+
+    // Index a score would take in the high score table, or None if it doesn't make the table
+    pub fn high_score_rank(&self, score: i32) -> Option<usize> {
+        if score <= 0 {
+            return None;
+        }
+
+        let rank = self.high_scores.iter().position(|high_score| score > high_score.score).unwrap_or(self.high_scores.len());
+        if rank < HIGH_SCORE_TABLE_SIZE {
+            Some(rank)
+        } else {
+            None
+        }
+    }
+
+    // Put a run into the high score table, returns its index in the table
+    pub fn add_high_score(&mut self, high_score: HighScore) -> Option<usize> {
+        let rank = self.high_score_rank(high_score.score)?;
+        self.high_scores.insert(rank, high_score);
+        self.high_scores.truncate(HIGH_SCORE_TABLE_SIZE);
+        Some(rank)
+    }
+}
+
+impl RunMode {
+    // The mode a run started now would be played in
+    pub fn current(preset: DifficultyPreset, adaptive_difficulty: &adaptive::AdaptiveDifficulty, control_scheme: input::ControlScheme) -> Self {
+        RunMode {
+            preset,
+            adaptive: adaptive_difficulty.enabled,
+            assisted: difficulty::assisted(control_scheme),
+        }
+    }
+
+    // A run without adaptive difficulty or an assisted control scheme
+    pub fn plain(preset: DifficultyPreset) -> Self {
+        RunMode {preset, adaptive: false, assisted: false}
+    }
+
+    // Preset name, followed by anything else which changes the difficulty, such as "Normal, Adaptive"
+    pub fn name(&self, locale: &locale::Locale) -> String {
+        let mut name = locale.get(self.preset.name());
+        if self.adaptive {
+            name = format!("{}, {}", name, locale.get("Adaptive"));
+        }
+        if self.assisted {
+            name = format!("{}, {}", name, locale.get("Assisted"));
+        }
+        name
+    }
+}
+
+impl HighScore {
+    pub fn mode(&self) -> RunMode {
+        RunMode {
+            preset: self.preset,
+            adaptive: self.adaptive,
+            assisted: difficulty::assisted(self.control_scheme),
+        }
+    }
+}
+
+impl LifetimeStats {
+    // Add a finished run to the totals, death is None when the run was quit or restarted before crashing
+    pub fn record_run(&mut self, score: i32, time_survived: f32, platforms_passed: u32, death: Option<Collision>) {
+        self.total_runs += 1;
+        self.total_platforms_passed += platforms_passed;
+        self.total_play_time += time_survived;
+        self.total_score += score as i64;
+        self.longest_run = self.longest_run.max(time_survived);
+
+        if let Some(collision) = death {
+            self.deaths.add(collision);
+        }
+    }
+
+    pub fn average_score(&self) -> f32 {
+        if self.total_runs == 0 {
+            return 0.0;
+        }
+        self.total_score as f32 / self.total_runs as f32
+    }
+}
+
+impl DeathCounts {
+    fn add(&mut self, collision: Collision) {
+        let count = match (collision.obstacle, collision.side) {
+            (Obstacle::Wall, generic::Direction::Left) => &mut self.wall_left,
+            (Obstacle::Wall, generic::Direction::Right) => &mut self.wall_right,
+            (Obstacle::Platform, generic::Direction::Left) => &mut self.platform_left,
+            (Obstacle::Platform, generic::Direction::Right) => &mut self.platform_right,
+        };
+        *count += 1;
+    }
+}
+
+// Location of the save file, inside the users data directory
+fn save_path() -> Option<PathBuf> {
+    data::user_file_path(dirs::data_dir(), SAVE_FILE_NAME)
+}
+
+// Save the score when it beats the best score for the mode the run was played in
+// Tutorial runs are played at a gentler difficulty, so they are never recorded
+fn record_best_score(
+    mut save_data: ResMut<SaveData>,
+    game: Res<game::Game>,
+    preset: Res<DifficultyPreset>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+    settings: Res<settings::Settings>,
+    tutorial: Res<game::tutorial::Tutorial>,
+) {
+    if tutorial.tutorial_run {
+        return;
+    }
+
+    let mode = RunMode::current(*preset, &adaptive_difficulty, settings.control_scheme);
+    if game.score > save_data.best_score(mode) {
+        save_data.mode_best_scores.insert(mode, game.score);
+        save_data.save();
+    }
+}
+
+// Todays date (UTC) as YYYY-MM-DD
+pub fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_date((seconds / 86400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Year, month and day of a number of days since 1970-01-01
+// From Howard Hinnant's civil_from_days algorithm
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // Months starting from March
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high_score(score: i32) -> HighScore {
+        HighScore {
+            name: "Test".to_string(),
+            score,
+            date: "2024-01-01".to_string(),
+            preset: DifficultyPreset::Normal,
+            seed: 0,
+            level: 1,
+            adaptive: false,
+            control_scheme: input::ControlScheme::Buttons,
+        }
+    }
+
+    #[test]
+    fn test_high_score_table_is_sorted_and_capped() {
+        let mut save_data = SaveData::default();
+        for score in 1..=HIGH_SCORE_TABLE_SIZE as i32 + 2 {
+            save_data.add_high_score(high_score(score));
+        }
+
+        assert_eq!(save_data.high_scores.len(), HIGH_SCORE_TABLE_SIZE);
+        assert_eq!(save_data.high_scores[0].score, HIGH_SCORE_TABLE_SIZE as i32 + 2);
+        assert_eq!(save_data.high_score_rank(1), None);
+        assert_eq!(save_data.add_high_score(high_score(7)), Some(6));
+    }
+
+    #[test]
+    fn test_lifetime_stats_record_runs() {
+        let mut lifetime = LifetimeStats::default();
+        assert_eq!(lifetime.average_score(), 0.0);
+
+        lifetime.record_run(10, 30.0, 10, Some(Collision {obstacle: Obstacle::Wall, side: generic::Direction::Left}));
+        lifetime.record_run(20, 50.0, 18, Some(Collision {obstacle: Obstacle::Platform, side: generic::Direction::Right}));
+        lifetime.record_run(0, 5.0, 0, None);
+
+        assert_eq!(lifetime.total_runs, 3);
+        assert_eq!(lifetime.total_platforms_passed, 28);
+        assert_eq!(lifetime.longest_run, 50.0);
+        assert_eq!(lifetime.average_score(), 10.0);
+        assert_eq!(lifetime.deaths, DeathCounts {wall_left: 1, wall_right: 0, platform_left: 0, platform_right: 1});
+    }
+
+    #[test]
+    fn test_old_best_scores_become_plain_best_scores() {
+        let mut save_data: SaveData = ron::from_str("(best_scores: {Normal: 12, Hard: 4})").unwrap();
+        save_data.mode_best_scores.insert(RunMode::plain(DifficultyPreset::Hard), 9);
+        save_data.migrate_best_scores();
+
+        assert_eq!(save_data.best_score(RunMode::plain(DifficultyPreset::Normal)), 12);
+        assert_eq!(save_data.best_score(RunMode::plain(DifficultyPreset::Hard)), 9);
+        assert_eq!(save_data.best_score(RunMode {preset: DifficultyPreset::Normal, adaptive: true, assisted: false}), 0);
+
+        // Saved without the old field, and the run modes read back
+        let saved = ron::to_string(&save_data).unwrap();
+        let loaded: SaveData = ron::from_str(&saved).unwrap();
+        assert!(loaded.best_scores.is_empty());
+        assert_eq!(loaded.mode_best_scores, save_data.mode_best_scores);
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(19723), (2024, 1, 1));
+        assert_eq!(civil_date(19782), (2024, 2, 29));
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use bevy::window::{PrimaryWindow, WindowMode};
+use crate::input::Action;
+use crate::settings::{self, WindowModeSetting};
 
 pub struct ScreenModePlugin;
 
@@ -9,18 +10,18 @@ impl Plugin for ScreenModePlugin {
     }
 }
 
-fn change_screen_mode(keyboard_input: Res<Input<KeyCode>>, mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
-    let mut window = window_query.get_single_mut().unwrap();
-
-    if keyboard_input.just_pressed(KeyCode::F11) {
-        window.mode = cycle_window_mode(window.mode);
+// The window mode is saved in the settings, so it is remembered after restarting
+fn change_screen_mode(actions: Res<Input<Action>>, mut settings: ResMut<settings::Settings>) {
+    if actions.just_pressed(Action::ToggleFullscreen) {
+        settings.window_mode = cycle_window_mode(settings.window_mode);
+        settings.save();
     }
 }
 
-fn cycle_window_mode(mode: WindowMode) -> WindowMode {
+fn cycle_window_mode(mode: WindowModeSetting) -> WindowModeSetting {
     match mode {
-        WindowMode::Windowed => WindowMode::BorderlessFullscreen,
-        WindowMode::BorderlessFullscreen => WindowMode::Windowed,
-        _ => WindowMode::Windowed,
+        WindowModeSetting::Windowed => WindowModeSetting::BorderlessFullscreen,
+        WindowModeSetting::BorderlessFullscreen => WindowModeSetting::Windowed,
+        _ => WindowModeSetting::Windowed,
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode, WindowResolution};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::{data, input, game, locale, ui};
+use game::player;
+
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+pub const RESOLUTIONS: [[u32; 2]; 4] = [[1280, 720], [1600, 900], [1920, 1080], [2560, 1440]]; // Window sizes to pick from when windowed
+pub const UI_SCALES: [f32; 4] = [0.75, 1.0, 1.25, 1.5];
+const VOLUME_STEPS: f32 = 10.0; // Volume goes from 0 to 1 in this many steps
+
+// Player preferences
+// Stored in the users config directory, and loaded before the window is created
+#[derive(Resource, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Settings {
+    pub bindings: input::InputBindings,
+    pub control_scheme: input::ControlScheme,
+    pub flight_model: player::FlightModel,
+
+    pub window_mode: WindowModeSetting,
+    pub resolution: [u32; 2], // Only used when windowed
+    pub vsync: bool,
+
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+
+    pub ui_scale: f32,
+    pub reduced_motion: bool, // Turns off animations which aren't needed to play, such as the score pop
+    pub countdown: bool, // Count down before the run starts and after unpausing
+
+    pub hud: HudSettings,
+    pub language: locale::Language,
+    pub ui_palette: ui::theme::UiPalette,
+}
+
+// Optional elements shown on the side of the HUD
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HudSettings {
+    pub level: bool,
+    pub level_progress: bool, // Progress bar to the next level
+    pub speed: bool,
+    pub best_score: bool, // Score compared to the personal best
+    pub elapsed_time: bool,
+}
+
+// Window modes which can be picked in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowModeSetting {
+    Windowed,
+    #[default]
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_settings.run_if(resource_changed::<Settings>()));
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            bindings: input::InputBindings::default(),
+            control_scheme: input::ControlScheme::default(),
+            flight_model: player::FlightModel::default(),
+
+            window_mode: WindowModeSetting::default(),
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+
+            ui_scale: 1.0,
+            reduced_motion: false,
+            countdown: true,
+
+            hud: HudSettings::default(),
+            language: locale::Language::default(),
+            ui_palette: ui::theme::UiPalette::default(),
+        }
+    }
+}
+
+impl Default for HudSettings {
+    fn default() -> Self {
+        HudSettings {
+            level: true,
+            level_progress: true,
+            speed: false,
+            best_score: true,
+            elapsed_time: false,
+        }
+    }
+}
+
+impl Settings {
+    // Load settings, or use the default settings when there are none
+    pub fn load() -> Self {
+        let mut settings: Settings = settings_path().and_then(|path| data::read_user_ron(&path)).unwrap_or_default();
+        settings.bindings.fill_missing_defaults();
+        settings
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = settings_path() {
+            data::write_user_ron(&path, self);
+        } else {
+            warn!("Couldn't find a config directory to save settings to");
+        }
+    }
+
+    // Primary window made from these settings
+    pub fn window(&self) -> Window {
+        Window {
+            mode: self.window_mode.window_mode(),
+            resolution: WindowResolution::new(self.resolution[0] as f32, self.resolution[1] as f32),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+
+    fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+}
+
+impl WindowModeSetting {
+    pub fn next(&self) -> Self {
+        match self {
+            WindowModeSetting::Windowed => WindowModeSetting::BorderlessFullscreen,
+            WindowModeSetting::BorderlessFullscreen => WindowModeSetting::Fullscreen,
+            WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::BorderlessFullscreen => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::BorderlessFullscreen => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+// Volume one step louder, wrapping back around to 0 after full volume
+pub fn next_volume(volume: f32) -> f32 {
+    let step = (volume * VOLUME_STEPS).round() + 1.0;
+
+    if step > VOLUME_STEPS {
+        0.0
+    } else {
+        step / VOLUME_STEPS
+    }
+}
+
+// Item after the current one in a list of options, or the first item if the current one isn't in the list
+pub fn next_option<T: Copy + PartialEq>(options: &[T], current: T) -> T {
+    let index = options.iter().position(|option| *option == current).map(|index| index + 1).unwrap_or(0);
+    options[index % options.len()]
+}
+
+// Location of the settings file, inside the users config directory
+fn settings_path() -> Option<PathBuf> {
+    data::user_file_path(dirs::config_dir(), SETTINGS_FILE_NAME)
+}
+
+// Apply settings to the window, audio and ui whenever they change
+fn apply_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    if let Ok(mut window) = window_query.get_single_mut() {
+        let mode = settings.window_mode.window_mode();
+        if window.mode != mode {
+            window.mode = mode;
+        }
+
+        if mode == WindowMode::Windowed && (window.resolution.width() as u32 != settings.resolution[0] || window.resolution.height() as u32 != settings.resolution[1]) {
+            window.resolution.set(settings.resolution[0] as f32, settings.resolution[1] as f32);
+        }
+
+        let present_mode = settings.present_mode();
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
+    }
+
+    // Sounds take the global volume when they start playing, so this only affects new sounds
+    *global_volume = GlobalVolume::new(settings.master_volume);
+    ui_scale.0 = settings.ui_scale as f64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_volume_wraps() {
+        assert_eq!(next_volume(0.0), 0.1);
+        assert_eq!(next_volume(0.9), 1.0);
+        assert_eq!(next_volume(1.0), 0.0);
+    }
+}
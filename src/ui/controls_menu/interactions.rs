@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use crate::{input, settings, locale};
+use input::{Action, Binding};
+use super::{layout, RebindCapture};
+
+// Start capturing a new binding for the action of the pressed binding button
+pub fn binding_button_interactions(
+    button_query: Query<(&Interaction, &layout::BindingButton), Changed<Interaction>>,
+    mut rebind_capture: ResMut<RebindCapture>,
+    mut status_text_query: Query<&mut Text, With<layout::RebindStatusText>>,
+    locale: Res<locale::Locale>,
+) {
+    for (interaction, binding_button) in button_query.iter() {
+        match interaction {
+            Interaction::Pressed => {
+                rebind_capture.action = Some(binding_button.0);
+                rebind_capture.armed = false;
+
+                if let Ok(mut text) = status_text_query.get_single_mut() {
+                    text.sections[0].value = locale.format("Rebinding {}, press Escape to cancel", &[&locale.get(binding_button.0.name())]);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+// Bind the next key or gamepad button pressed to the action being rebound
+// Bindings already used by another action are refused, so one press never triggers two actions
+pub fn capture_rebind(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut rebind_capture: ResMut<RebindCapture>,
+    mut settings: ResMut<settings::Settings>,
+    mut status_text_query: Query<&mut Text, With<layout::RebindStatusText>>,
+    locale: Res<locale::Locale>,
+) {
+    let action = match rebind_capture.action {
+        Some(action) => action,
+        None => return,
+    };
+
+    if !rebind_capture.armed {
+        rebind_capture.armed = true;
+        return;
+    }
+
+    let pressed = keyboard_input.get_just_pressed().next().map(|key| Binding::Key(*key))
+        .or_else(|| gamepad_buttons.get_just_pressed().next().map(|button| Binding::GamepadButton(button.button_type)));
+
+    let binding = match pressed {
+        Some(binding) => binding,
+        None => return,
+    };
+
+    // Escape cancels, unless it is being bound to pause
+    let status = if binding == Binding::Key(KeyCode::Escape) && action != Action::Pause {
+        locale.get("Rebinding cancelled")
+    } else if let Some(conflict) = settings.bindings.conflict(binding, action) {
+        locale.format("{} is already used by {}", &[&binding.name(), &locale.get(conflict.name())])
+    } else {
+        settings.bindings.rebind(action, binding);
+        settings.save();
+        locale.format("{} bound to {}", &[&locale.get(action.name()), &binding.name()])
+    };
+
+    rebind_capture.action = None;
+    if let Ok(mut text) = status_text_query.get_single_mut() {
+        text.sections[0].value = status;
+    }
+}
+
+pub fn reset_bindings_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::ResetBindingsButton>)>,
+    mut settings: ResMut<settings::Settings>,
+    mut rebind_capture: ResMut<RebindCapture>,
+    mut status_text_query: Query<&mut Text, With<layout::RebindStatusText>>,
+    locale: Res<locale::Locale>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => {
+                settings.bindings = input::InputBindings::default();
+                settings.save();
+                rebind_capture.action = None;
+
+                if let Ok(mut text) = status_text_query.get_single_mut() {
+                    text.sections[0].value = locale.get("Bindings reset to defaults");
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+pub fn cancel_rebind(mut rebind_capture: ResMut<RebindCapture>) {
+    rebind_capture.action = None;
+}
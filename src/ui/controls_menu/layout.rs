@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use crate::{ui, input, settings, locale};
+use ui::{helpers, styles, theme};
+use input::Action;
+use super::RebindCapture;
+
+#[derive(Component)]
+pub struct ControlsMenu;
+
+// Button showing the bindings of an action, pressing it starts capturing a new binding
+#[derive(Component)]
+pub struct BindingButton(pub Action);
+
+#[derive(Component)]
+pub struct BindingButtonText(pub Action);
+
+// Shows conflicts and other messages about rebinding
+#[derive(Component)]
+pub struct RebindStatusText;
+
+#[derive(Component)]
+pub struct ResetBindingsButton;
+
+
+pub fn spawn_controls_menu(mut commands: Commands, asset_server: Res<AssetServer>, locale: Res<locale::Locale>, settings: Res<settings::Settings>) {
+    commands.spawn(
+
+        (
+            ControlsMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
+
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+
+                    row_gap: Val::Percent(0.5),
+
+                    ..default()
+                },
+                ..default()
+            }
+        )
+    )
+
+    .with_children(|parent| {
+
+        // Title
+        parent.spawn(
+            helpers::localized_text("Controls", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
+        );
+
+        // One row for every action, with its name and a button showing its bindings
+        for action in Action::ALL {
+            parent.spawn(
+                NodeBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(5.5),
+                        column_gap: Val::Percent(1.0),
+                        ..default()
+                    },
+                    ..default()
+                }
+            ).with_children(|parent| {
+                parent.spawn(
+                    helpers::text(&locale.get(action.name()), styles::SMALL_TEXT_STYLE, &asset_server)
+                        .with_style(Style {
+                            width: Val::Percent(15.0),
+                            ..default()
+                        })
+                );
+
+                parent.spawn(
+                    (
+                        ui::GenericButton,
+                        BindingButton(action),
+                        helpers::button(styles::LIST_BUTTON_STYLE),
+                    )
+                )
+                .with_children(|parent| {
+                    parent.spawn(
+                        (
+                            BindingButtonText(action),
+                            helpers::text(&bindings_text(&settings.bindings, action, &locale), styles::LIST_BUTTON_STYLE.text_style.unwrap(), &asset_server),
+                        )
+                    );
+                });
+            });
+        }
+
+        parent.spawn(
+            (
+                RebindStatusText,
+                helpers::localized_text("Select an action to rebind it", styles::SMALL_TEXT_STYLE, &asset_server, &locale),
+            )
+        );
+
+        // Reset bindings button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ResetBindingsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Reset Defaults", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Back button, returns to the menu the controls screen was opened from
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SubMenuBackButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Back", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+    });
+}
+
+pub fn despawn_controls_menu(mut commands: Commands, menu_query: Query<Entity, With<ControlsMenu>>) {
+    if let Ok(menu_entity) = menu_query.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
+
+// Keep the binding buttons up to date with the settings, and show which action is being rebound
+pub fn update_binding_texts(
+    settings: Res<settings::Settings>,
+    rebind_capture: Res<RebindCapture>,
+    locale: Res<locale::Locale>,
+    mut text_query: Query<(&mut Text, &BindingButtonText)>,
+) {
+    if !settings.is_changed() && !rebind_capture.is_changed() {
+        return;
+    }
+
+    for (mut text, binding_text) in text_query.iter_mut() {
+        text.sections[0].value = if rebind_capture.action == Some(binding_text.0) {
+            locale.get("Press a key or button...")
+        } else {
+            bindings_text(&settings.bindings, binding_text.0, &locale)
+        };
+    }
+}
+
+fn bindings_text(bindings: &input::InputBindings, action: Action, locale: &locale::Locale) -> String {
+    let names: Vec<String> = bindings.get(action).iter().map(|binding| binding.name()).collect();
+
+    if names.is_empty() {
+        locale.get("Unbound")
+    } else {
+        names.join(", ")
+    }
+}
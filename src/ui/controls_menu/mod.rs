@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::input::Action;
+use super::SubMenu;
+
+pub mod layout;
+pub mod interactions;
+
+// Action waiting for a key or button press to rebind it
+#[derive(Resource, Default)]
+pub struct RebindCapture {
+    pub action: Option<Action>,
+    armed: bool, // False on the frame capturing starts, so the press which started it isn't captured
+}
+
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(RebindCapture::default())
+            .add_systems(OnEnter(SubMenu::Controls), layout::spawn_controls_menu)
+            .add_systems(OnExit(SubMenu::Controls), (layout::despawn_controls_menu, interactions::cancel_rebind))
+            .add_systems(Update, (
+                interactions::binding_button_interactions,
+                interactions::capture_rebind,
+                interactions::reset_bindings_button_interactions,
+                layout::update_binding_texts,
+            ).chain().run_if(in_state(SubMenu::Controls)));
+    }
+}
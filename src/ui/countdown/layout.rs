@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::{ui, input, locale, settings};
+use ui::{helpers, styles, theme};
+use input::Action;
+use super::{countdown_text, COUNTDOWN_SECONDS};
+
+#[derive(Component)]
+pub struct Countdown;
+
+#[derive(Component)]
+pub struct CountdownText;
+
+pub fn spawn_countdown(mut commands: Commands, asset_server: Res<AssetServer>, locale: Res<locale::Locale>, settings: Res<settings::Settings>) {
+    commands.spawn(
+
+        (
+            Countdown,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
+
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+
+                    row_gap: Val::Percent(1.0),
+
+                    ..default()
+                },
+                z_index: ZIndex::Global(1), // Drawn over the hud, which can be spawned after the countdown
+                ..default()
+            }
+        )
+    )
+
+    .with_children(|parent| {
+        parent.spawn(
+            (
+                CountdownText,
+                helpers::text(&countdown_text(COUNTDOWN_SECONDS), styles::TITLE_TEXT_STYLE, &asset_server),
+            )
+        );
+
+        // Shows the first binding for select, so the hint stays right after rebinding
+        let skip_binding = settings.bindings.get(Action::MenuSelect).first().map(|binding| binding.name()).unwrap_or_default();
+        parent.spawn(
+            helpers::text(&locale.format("Press {} to skip", &[&skip_binding]), styles::SMALL_TEXT_STYLE, &asset_server)
+        );
+    });
+}
+
+pub fn despawn_countdown(mut commands: Commands, countdown_query: Query<Entity, With<Countdown>>) {
+    if let Ok(countdown_entity) = countdown_query.get_single() {
+        commands.entity(countdown_entity).despawn_recursive();
+    }
+}
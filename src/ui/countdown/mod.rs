@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::{AppState, game::GameState, input::Action, settings};
+
+pub mod layout;
+
+const COUNTDOWN_SECONDS: f32 = 3.0;
+
+#[derive(Resource)]
+struct CountdownTimer(Timer);
+
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(CountdownTimer(Timer::from_seconds(COUNTDOWN_SECONDS, TimerMode::Once)))
+            .add_systems(OnEnter(AppState::GameSetup), start_run_countdown)
+            .add_systems(OnEnter(GameState::Countdown), (reset_countdown, layout::spawn_countdown))
+            .add_systems(OnExit(GameState::Countdown), layout::despawn_countdown)
+            .add_systems(Update, tick_countdown.run_if(in_state(GameState::Countdown)).run_if(in_state(AppState::Game)));
+    }
+}
+
+// State the game goes to when it starts or is unpaused, the countdown unless it is turned off
+pub fn resume_state(settings: &settings::Settings) -> GameState {
+    if settings.countdown {
+        GameState::Countdown
+    } else {
+        GameState::Running
+    }
+}
+
+fn start_run_countdown(settings: Res<settings::Settings>, mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(resume_state(&settings));
+}
+
+fn reset_countdown(mut countdown_timer: ResMut<CountdownTimer>) {
+    countdown_timer.0.reset();
+}
+
+// Count down, then start the game, the select and one button actions skip the rest of the countdown
+fn tick_countdown(
+    mut countdown_timer: ResMut<CountdownTimer>,
+    mut text_query: Query<&mut Text, With<layout::CountdownText>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    actions: Res<Input<Action>>,
+    time: Res<Time>,
+) {
+    countdown_timer.0.tick(time.delta());
+
+    if countdown_timer.0.finished() || actions.any_just_pressed([Action::MenuSelect, Action::OneButton]) {
+        next_state.set(GameState::Running);
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = countdown_text(countdown_timer.0.remaining_secs());
+    }
+}
+
+// Whole seconds left, rounded up so the countdown shows 3, 2, 1
+pub fn countdown_text(seconds_left: f32) -> String {
+    (seconds_left.ceil() as i32).max(1).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_text() {
+        assert_eq!(countdown_text(3.0), "3");
+        assert_eq!(countdown_text(2.01), "3");
+        assert_eq!(countdown_text(1.5), "2");
+        assert_eq!(countdown_text(0.0), "1");
+    }
+}
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use std::cmp::Ordering;
+use crate::input::{self, Action};
+use super::{theme, controls_menu, GenericButton};
+
+// Button selected with the menu navigation actions, so menus can be used without a mouse
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    pub focused: Option<Entity>,
+    pressed: Option<Entity>, // Button pressed with MenuSelect, released on the next frame
+}
+
+pub struct MenuFocusPlugin;
+
+impl Plugin for MenuFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(MenuFocus::default())
+
+            // Runs after bevy updates Interaction, so button systems see presses made with MenuSelect on the same frame
+            .add_systems(PreUpdate, navigate_menus.after(UiSystem::Focus).after(input::update_actions))
+            .add_systems(Update, highlight_focused_button.after(super::button_interactions));
+    }
+}
+
+// Move focus between buttons with MenuUp and MenuDown, and press the focused button with MenuSelect
+// The steering actions also move focus, left to the previous button and right to the next, so WASD and the arrow keys all work
+fn navigate_menus(
+    actions: Res<Input<Action>>,
+    rebind_capture: Res<controls_menu::RebindCapture>,
+    mut focus: ResMut<MenuFocus>,
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), With<GenericButton>>,
+) {
+    if let Some(entity) = focus.pressed.take() {
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(entity) {
+            *interaction = Interaction::None;
+        }
+    }
+
+    // The next press is being captured as a new binding, so it shouldn't also navigate
+    if rebind_capture.action.is_some() {
+        return;
+    }
+
+    // Buttons in the order they appear on screen, top to bottom then left to right
+    let mut buttons: Vec<(Entity, Vec3)> = button_query.iter().map(|(entity, transform, _)| (entity, transform.translation())).collect();
+    buttons.sort_by(|(_, a), (_, b)| a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal).then(a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal)));
+
+    // Forget focus once the focused button is despawned, such as when its menu closes
+    let focused_index = focus.focused.and_then(|focused| buttons.iter().position(|(entity, _)| *entity == focused));
+    if focused_index.is_none() {
+        focus.focused = None;
+    }
+
+    if buttons.is_empty() {
+        return;
+    }
+
+    let step: i32 = if actions.any_just_pressed([Action::MenuDown, Action::SteerRight]) {
+        1
+    } else if actions.any_just_pressed([Action::MenuUp, Action::SteerLeft]) {
+        -1
+    } else {
+        0
+    };
+
+    if step != 0 {
+        let next_index = match focused_index {
+            Some(index) => (index as i32 + step).rem_euclid(buttons.len() as i32) as usize,
+            None => 0, // The first navigation press focuses the first button
+        };
+        focus.focused = Some(buttons[next_index].0);
+    }
+
+    if actions.just_pressed(Action::MenuSelect) {
+        if let Some(focused) = focus.focused {
+            if let Ok((_, _, mut interaction)) = button_query.get_mut(focused) {
+                *interaction = Interaction::Pressed;
+                focus.pressed = Some(focused);
+            }
+        }
+    }
+}
+
+// Show the focused button with the hover colour, unless the mouse is interacting with it
+fn highlight_focused_button(
+    focus: Res<MenuFocus>,
+    ui_theme: Res<theme::UiTheme>,
+    mut button_query: Query<(Entity, &Interaction, &mut BackgroundColor), With<GenericButton>>,
+) {
+    for (entity, interaction, mut background_color) in button_query.iter_mut() {
+        if *interaction != Interaction::None {
+            continue;
+        }
+
+        let color: BackgroundColor = if focus.focused == Some(entity) {
+            ui_theme.button_color(Interaction::Hovered).into()
+        } else {
+            ui_theme.button_color(Interaction::None).into()
+        };
+        if background_color.0 != color.0 {
+            *background_color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(app: &mut App, action: Action) {
+        let mut actions = app.world.resource_mut::<Input<Action>>();
+        actions.clear();
+        actions.release_all();
+        actions.press(action);
+        app.update();
+    }
+
+    #[test]
+    fn test_navigation_wraps_and_selects() {
+        let mut app = App::new();
+        app
+            .insert_resource(Input::<Action>::default())
+            .insert_resource(controls_menu::RebindCapture::default())
+            .insert_resource(MenuFocus::default())
+            .add_systems(Update, navigate_menus);
+
+        // Spawned out of order, ui y increases down the screen
+        let bottom = app.world.spawn((GenericButton, GlobalTransform::from_xyz(0.0, 200.0, 0.0), Interaction::None)).id();
+        let top = app.world.spawn((GenericButton, GlobalTransform::from_xyz(0.0, 0.0, 0.0), Interaction::None)).id();
+
+        press(&mut app, Action::MenuDown);
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(top));
+
+        press(&mut app, Action::SteerRight);
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(bottom));
+
+        press(&mut app, Action::MenuDown);
+        assert_eq!(app.world.resource::<MenuFocus>().focused, Some(top));
+
+        press(&mut app, Action::MenuSelect);
+        assert_eq!(*app.world.get::<Interaction>(top).unwrap(), Interaction::Pressed);
+    }
+}
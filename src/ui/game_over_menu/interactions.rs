@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use crate::{AppState, GameCleanupEvent};
-use super::layout;
+use crate::{AppState, GameCleanupEvent, save, input, locale};
+use super::{layout, PendingHighScore, MAX_NAME_LENGTH};
 
 // Restart the game
 pub fn restart_button_interactions(
@@ -18,4 +18,88 @@ pub fn restart_button_interactions(
             _ => (), 
         }
     }
-}
\ No newline at end of file
+}
+
+// Type a name for the high score table, Enter saves it
+#[allow(clippy::too_many_arguments)]
+pub fn type_high_score_name(
+    mut received_characters: EventReader<ReceivedCharacter>,
+    keyboard_input: Res<Input<KeyCode>>,
+    locale: Res<locale::Locale>,
+    mut pending: ResMut<PendingHighScore>,
+    mut save_data: ResMut<save::SaveData>,
+    mut text_entry: ResMut<input::TextEntry>,
+    mut name_text_query: Query<&mut Text, With<layout::HighScoreNameText>>,
+    save_button_query: Query<Entity, With<layout::SaveScoreButton>>,
+    mut commands: Commands,
+) {
+    // Characters from the end of the run are still waiting to be read, and held keys repeat, so both are dropped until every held key is released
+    if !pending.ready {
+        received_characters.clear();
+        pending.held_keys.retain(|key| keyboard_input.pressed(*key));
+        pending.ready = pending.held_keys.is_empty();
+        return;
+    }
+
+    let rank = pending.rank;
+    if let Some(high_score) = &mut pending.high_score {
+        for event in received_characters.read() {
+            if !event.char.is_control() && high_score.name.chars().count() < MAX_NAME_LENGTH {
+                high_score.name.push(event.char);
+            }
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Back) {
+            high_score.name.pop();
+        }
+
+        if let Ok(mut text) = name_text_query.get_single_mut() {
+            text.sections[0].value = layout::name_entry_text(&high_score.name, rank, &locale);
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Return) {
+            save_high_score(&mut pending, &mut save_data, &mut text_entry, &mut name_text_query, &save_button_query, &mut commands, &locale);
+        }
+    }
+}
+
+// Save the high score with the name typed so far
+#[allow(clippy::too_many_arguments)]
+pub fn save_score_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::SaveScoreButton>)>,
+    locale: Res<locale::Locale>,
+    mut pending: ResMut<PendingHighScore>,
+    mut save_data: ResMut<save::SaveData>,
+    mut text_entry: ResMut<input::TextEntry>,
+    mut name_text_query: Query<&mut Text, With<layout::HighScoreNameText>>,
+    save_button_query: Query<Entity, With<layout::SaveScoreButton>>,
+    mut commands: Commands,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => save_high_score(&mut pending, &mut save_data, &mut text_entry, &mut name_text_query, &save_button_query, &mut commands, &locale),
+            _ => (),
+        }
+    }
+}
+
+// Save the pending high score, then replace name entry with where the run was placed
+fn save_high_score(
+    pending: &mut PendingHighScore,
+    save_data: &mut save::SaveData,
+    text_entry: &mut input::TextEntry,
+    name_text_query: &mut Query<&mut Text, With<layout::HighScoreNameText>>,
+    save_button_query: &Query<Entity, With<layout::SaveScoreButton>>,
+    commands: &mut Commands,
+    locale: &locale::Locale,
+) {
+    if let Some(rank) = super::submit_high_score(pending, save_data, text_entry) {
+        if let Ok(mut text) = name_text_query.get_single_mut() {
+            text.sections[0].value = locale.format("Saved in place #{}", &[&(rank + 1)]);
+        }
+    }
+
+    if let Ok(button_entity) = save_button_query.get_single() {
+        commands.entity(button_entity).despawn_recursive();
+    }
+}
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
-use crate::ui;
-use ui::{helpers, styles};
+use crate::{ui, game, locale};
+use ui::{helpers, styles, theme};
+use game::{stats, corridor, tutorial};
+use super::PendingHighScore;
 
 #[derive(Component)]
 pub struct GameOverMenu;
@@ -9,11 +11,28 @@ pub struct GameOverMenu;
 #[derive(Component)]
 pub struct RestartButton;
 
-pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+pub struct HighScoreNameText;
+
+#[derive(Component)]
+pub struct SaveScoreButton;
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_game_over_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    locale: Res<locale::Locale>,
+    pending: Res<PendingHighScore>,
+    game: Res<game::Game>,
+    run_stats: Res<stats::RunStats>,
+    corridor: Res<corridor::Corridor>,
+    tutorial: Res<tutorial::Tutorial>,
+) {
     commands.spawn(
 
         (
             GameOverMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
 
             NodeBundle {
                 style: Style {
@@ -28,7 +47,6 @@ pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
 
                     ..default()
                 },
-                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
                 ..default()
             }
         )
@@ -43,7 +61,7 @@ pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     margin: UiRect {
-                        bottom: Val::Percent(10.0),
+                        bottom: Val::Percent(2.0),
                         ..default()
                     },
                     ..default()
@@ -52,9 +70,90 @@ pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
             }
         ).with_children(|parent| {
             parent.spawn(
-                helpers::text("Game Over", styles::TITLE_TEXT_STYLE, &asset_server)
+                helpers::localized_text("Game Over", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
             );
         });
+
+        // Summary of the run
+        let best_text = if run_stats.beat_best(game.score) && !tutorial.tutorial_run {
+            locale.format("{} (New best!)", &[&game.score])
+        } else {
+            run_stats.previous_best.to_string()
+        };
+
+        let summary = [
+            ("Score", game.score.to_string()),
+            ("Level", game.difficulty.level.to_string()),
+            ("Time survived", stats::time_text(run_stats.time_survived)),
+            ("Depth", format!("{:.0}m", run_stats.depth)),
+            ("Platforms passed", run_stats.platforms_passed.to_string()),
+            ("Near misses", run_stats.near_misses.to_string()),
+            ("Personal best", best_text),
+            ("Seed", corridor.seed.to_string()),
+        ];
+
+        parent.spawn(
+            (
+                theme::ThemedBackground(theme::ThemeColor::Foreground),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        width: Val::Percent(40.0),
+                        padding: UiRect::all(Val::Percent(1.0)),
+                        margin: UiRect {
+                            bottom: Val::Percent(2.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ..default()
+                },
+            )
+        ).with_children(|parent| {
+            for (label, value) in summary {
+                parent.spawn(
+                    NodeBundle {
+                        style: Style {
+                            justify_content: JustifyContent::SpaceBetween,
+                            width: Val::Percent(100.0),
+                            ..default()
+                        },
+                        ..default()
+                    }
+                ).with_children(|parent| {
+                    parent.spawn(
+                        helpers::localized_text(label, styles::SMALL_TEXT_STYLE, &asset_server, &locale)
+                    );
+                    parent.spawn(
+                        helpers::text(&value, styles::SMALL_TEXT_STYLE, &asset_server)
+                    );
+                });
+            }
+        });
+
+        // Name entry when the run made the high score table
+        if pending.high_score.is_some() {
+            parent.spawn(
+                (
+                    HighScoreNameText,
+                    helpers::text(&name_entry_text("", pending.rank, &locale), styles::BODY_TEXT_STYLE, &asset_server),
+                )
+            );
+
+            parent.spawn(
+                (
+                    ui::GenericButton,
+                    SaveScoreButton,
+                    helpers::button(styles::BUTTON_STYLE),
+                )
+            )
+            .with_children(|parent| {
+                parent.spawn(
+                    helpers::localized_text("Save Score", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+                );
+            });
+        }
         
         // Restart game button
         parent.spawn(
@@ -66,7 +165,7 @@ pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Restart", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Restart", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
             );
         });
 
@@ -80,7 +179,7 @@ pub fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServe
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Main Menu", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Main Menu", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
             );
         });
 
@@ -91,4 +190,8 @@ pub fn despawn_game_over_menu(mut commands: Commands, hud_query: Query<Entity, W
     if let Ok(hud_entity) = hud_query.get_single() {
         commands.entity(hud_entity).despawn_recursive();
     }
-}
\ No newline at end of file
+}
+
+pub fn name_entry_text(name: &str, rank: usize, locale: &locale::Locale) -> String {
+    locale.format("High score #{}! Name: {}_", &[&(rank + 1), &name])
+}
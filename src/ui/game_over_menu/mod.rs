@@ -1,18 +1,160 @@
 use bevy::prelude::*;
 
-use crate::game::GameState;
+use crate::{game, save, input, settings};
+use game::{GameState, corridor, difficulty, tutorial, adaptive};
 
 pub mod layout;
 pub mod interactions;
 
+const DEFAULT_HIGH_SCORE_NAME: &str = "Player";
+pub const MAX_NAME_LENGTH: usize = 12;
+
+// The run which just ended, while it waits for a name before going into the high score table
+#[derive(Resource, Default)]
+pub struct PendingHighScore {
+    pub high_score: Option<save::HighScore>, // None when the run didn't make the table, or has been saved
+    pub rank: usize,
+    pub held_keys: Vec<KeyCode>, // Keys held when the run ended, such as a steering key, typing is ignored until they are released
+    pub ready: bool, // Set once the held keys are released, so characters typed during the run don't end up in the name
+}
+
 pub struct GameOverMenuPlugin;
 
 impl Plugin for GameOverMenuPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(GameState::GameOver), layout::spawn_game_over_menu)
-            .add_systems(OnExit(GameState::GameOver), layout::despawn_game_over_menu)
-            .add_systems(Update, interactions::restart_button_interactions.run_if(in_state(GameState::GameOver)));
+            .insert_resource(PendingHighScore::default())
+            .add_systems(OnEnter(GameState::GameOver), (prepare_high_score, hold_name_entry, layout::spawn_game_over_menu).chain())
+            .add_systems(OnExit(GameState::GameOver), (save_pending_high_score, layout::despawn_game_over_menu))
+            .add_systems(Update, (interactions::restart_button_interactions, interactions::type_high_score_name, interactions::save_score_button_interactions).run_if(in_state(GameState::GameOver)));
+    }
+}
+
+// Check if the run made the high score table, and start name entry if it did
+#[allow(clippy::too_many_arguments)]
+fn prepare_high_score(
+    game: Res<game::Game>,
+    corridor: Res<corridor::Corridor>,
+    preset: Res<difficulty::DifficultyPreset>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+    settings: Res<settings::Settings>,
+    save_data: Res<save::SaveData>,
+    mut pending: ResMut<PendingHighScore>,
+    mut text_entry: ResMut<input::TextEntry>,
+    tutorial: Res<tutorial::Tutorial>,
+) {
+    *pending = PendingHighScore::default();
+
+    // Tutorial runs are played at a gentler difficulty, so they don't go into the table
+    if tutorial.tutorial_run {
+        return;
+    }
+
+    if let Some(rank) = save_data.high_score_rank(game.score) {
+        pending.rank = rank;
+        pending.high_score = Some(save::HighScore {
+            name: String::new(),
+            score: game.score,
+            date: save::today(),
+            preset: *preset,
+            seed: corridor.seed,
+            level: game.difficulty.level,
+            adaptive: adaptive_difficulty.enabled,
+            control_scheme: settings.control_scheme,
+        });
+        text_entry.active = true;
+    }
+}
+
+// Keys still held from the run keep sending characters while held, so name entry waits until they are released
+fn hold_name_entry(keyboard_input: Res<Input<KeyCode>>, mut pending: ResMut<PendingHighScore>) {
+    pending.held_keys = keyboard_input.get_pressed().copied().collect();
+    pending.ready = false;
+}
+
+// Put the pending run into the high score table, returns its place in the table
+// An empty name is saved as DEFAULT_HIGH_SCORE_NAME
+pub fn submit_high_score(pending: &mut PendingHighScore, save_data: &mut save::SaveData, text_entry: &mut input::TextEntry) -> Option<usize> {
+    let mut high_score = pending.high_score.take()?;
+    text_entry.active = false;
+
+    if high_score.name.trim().is_empty() {
+        high_score.name = DEFAULT_HIGH_SCORE_NAME.to_string();
     }
+
+    let rank = save_data.add_high_score(high_score);
+    save_data.save();
+    rank
 }
 
+// Runs aren't lost when leaving the game over menu without saving
+fn save_pending_high_score(
+    mut pending: ResMut<PendingHighScore>,
+    mut save_data: ResMut<save::SaveData>,
+    mut text_entry: ResMut<input::TextEntry>,
+) {
+    submit_high_score(&mut pending, &mut save_data, &mut text_entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::input::keyboard::KeyboardInput;
+    use crate::locale;
+
+    fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+        app.world.send_event(KeyboardInput {scan_code: 0, key_code: Some(key_code), state, window: Entity::PLACEHOLDER});
+    }
+
+    fn type_character(app: &mut App, char: char) {
+        app.world.send_event(ReceivedCharacter {window: Entity::PLACEHOLDER, char});
+        app.update();
+    }
+
+    fn name(app: &App) -> String {
+        app.world.resource::<PendingHighScore>().high_score.as_ref().unwrap().name.clone()
+    }
+
+    #[test]
+    fn test_steering_key_held_into_game_over_is_not_typed() {
+        let mut app = App::new();
+        app
+            .add_plugins((MinimalPlugins, InputPlugin))
+            .add_state::<GameState>()
+            .add_event::<ReceivedCharacter>()
+            .insert_resource(locale::Locale::default())
+            .insert_resource(save::SaveData::default())
+            .insert_resource(input::TextEntry::default())
+            .insert_resource(PendingHighScore::default())
+            .add_systems(OnEnter(GameState::GameOver), hold_name_entry)
+            .add_systems(Update, interactions::type_high_score_name.run_if(in_state(GameState::GameOver)));
+
+        // Steering right when the plane crashes
+        send_key(&mut app, KeyCode::D, ButtonState::Pressed);
+        type_character(&mut app, 'd');
+
+        app.world.resource_mut::<PendingHighScore>().high_score = Some(save::HighScore {
+            name: String::new(),
+            score: 10,
+            date: save::today(),
+            preset: difficulty::DifficultyPreset::default(),
+            seed: 0,
+            level: 1,
+            adaptive: false,
+            control_scheme: input::ControlScheme::default(),
+        });
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::GameOver);
+        type_character(&mut app, 'd');
+
+        // Key repeat while D stays held
+        type_character(&mut app, 'd');
+        type_character(&mut app, 'd');
+        assert_eq!(name(&app), "");
+
+        send_key(&mut app, KeyCode::D, ButtonState::Released);
+        app.update();
+        type_character(&mut app, 'a');
+        assert_eq!(name(&app), "a");
+    }
+}
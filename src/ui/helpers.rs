@@ -1,28 +1,53 @@
 use bevy::prelude::*;
-use crate::ui::styles;
+use crate::ui::{styles, theme};
+use crate::locale;
+
+// Text_Bundle which is coloured by the ui theme
+#[derive(Bundle)]
+pub struct ThemedTextBundle {
+    pub text: TextBundle,
+    pub themed_text: theme::ThemedText,
+}
+
+impl ThemedTextBundle {
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.text.style = style;
+        self
+    }
+}
 
 // Creates a new Text_Bundle with text_style
-pub fn text(text: &str, text_style: styles::TextStyle, asset_server: &AssetServer) -> TextBundle {
-    TextBundle {
-        text: Text {
-            sections: vec![
-                TextSection::new(
-                    text,
-                    TextStyle {
-                        font: asset_server.load(text_style.font),
-                        font_size: text_style.size,
-                        color: Color::hex(text_style.color_hex).unwrap(),
-                    }
-                )
-            ],
-            alignment: text_style.allignment,
+// It is transparent until the ui theme colours it, which happens before it is first drawn
+pub fn text(text: &str, text_style: styles::TextStyle, asset_server: &AssetServer) -> ThemedTextBundle {
+    ThemedTextBundle {
+        text: TextBundle {
+            text: Text {
+                sections: vec![
+                    TextSection::new(
+                        text,
+                        TextStyle {
+                            font: asset_server.load(text_style.font),
+                            font_size: text_style.size,
+                            color: Color::NONE,
+                        }
+                    )
+                ],
+                alignment: text_style.allignment,
+                ..default()
+            },
             ..default()
         },
-        ..default()
+        themed_text: theme::ThemedText(text_style.color),
     }
 }
 
+// Creates a new Text_Bundle showing english translated to the current language, which is translated again if the language changes
+pub fn localized_text(english: &'static str, text_style: styles::TextStyle, asset_server: &AssetServer, locale: &locale::Locale) -> (locale::LocalizedText, ThemedTextBundle) {
+    (locale::LocalizedText(english), text(&locale.get(english), text_style, asset_server))
+}
+
 // Creates a new ButtonBundle with button_style
+// Its colour is set by the ui theme from its interaction state
 pub fn button(button_style: styles::ButtonStyle) -> ButtonBundle {
     ButtonBundle {
         style: Style {
@@ -33,7 +58,7 @@ pub fn button(button_style: styles::ButtonStyle) -> ButtonBundle {
             height: button_style.height,
             ..Style::DEFAULT
         },
-        background_color: Color::hex(button_style.color_hex).unwrap().into(),
+        background_color: Color::NONE.into(),
         ..ButtonBundle::default()
     }
 }
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::{ui, save, locale};
+use ui::{helpers, styles, theme};
+
+#[derive(Component)]
+pub struct HighScoresMenu;
+
+const COLUMNS: [(&str, f32); 8] = [
+    ("#", 4.0),
+    ("Name", 16.0),
+    ("Score", 8.0),
+    ("Level", 8.0),
+    ("Mode", 16.0),
+    ("Controls", 12.0),
+    ("Date", 12.0),
+    ("Seed", 18.0),
+];
+
+pub fn spawn_high_scores_menu(mut commands: Commands, asset_server: Res<AssetServer>, locale: Res<locale::Locale>, save_data: Res<save::SaveData>) {
+    commands.spawn(
+
+        (
+            HighScoresMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
+
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+
+                    row_gap: Val::Percent(0.5),
+
+                    ..default()
+                },
+                ..default()
+            }
+        )
+    )
+
+    .with_children(|parent| {
+
+        // Title
+        parent.spawn(
+            helpers::localized_text("High Scores", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
+        );
+
+        if save_data.high_scores.is_empty() {
+            parent.spawn(
+                helpers::localized_text("No high scores yet", styles::BODY_TEXT_STYLE, &asset_server, &locale)
+            );
+        } else {
+            spawn_row(parent, COLUMNS.map(|(name, _)| locale.get(name)), &asset_server);
+        }
+
+        // One row for every entry in the table
+        for (rank, high_score) in save_data.high_scores.iter().enumerate() {
+            spawn_row(parent, [
+                (rank + 1).to_string(),
+                high_score.name.clone(),
+                high_score.score.to_string(),
+                high_score.level.to_string(),
+                high_score.mode().name(&locale),
+                locale.get(high_score.control_scheme.name()),
+                high_score.date.clone(),
+                high_score.seed.to_string(),
+            ], &asset_server);
+        }
+
+        // Back button, returns to the main menu
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SubMenuBackButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Back", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+    });
+}
+
+// A row of the table, each value sits in its column
+fn spawn_row(parent: &mut ChildBuilder, values: [String; COLUMNS.len()], asset_server: &AssetServer) {
+    parent.spawn(
+        NodeBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: Val::Percent(100.0),
+                height: Val::Percent(5.0),
+                ..default()
+            },
+            ..default()
+        }
+    ).with_children(|parent| {
+        for (value, (_, width)) in values.iter().zip(COLUMNS) {
+            parent.spawn(
+                helpers::text(value, styles::SMALL_TEXT_STYLE, asset_server)
+                    .with_style(Style {
+                        width: Val::Percent(width),
+                        ..default()
+                    })
+            );
+        }
+    });
+}
+
+pub fn despawn_high_scores_menu(mut commands: Commands, menu_query: Query<Entity, With<HighScoresMenu>>) {
+    if let Ok(menu_entity) = menu_query.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use super::SubMenu;
+
+pub mod layout;
+
+pub struct HighScoresMenuPlugin;
+
+impl Plugin for HighScoresMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(SubMenu::HighScores), layout::spawn_high_scores_menu)
+            .add_systems(OnExit(SubMenu::HighScores), layout::despawn_high_scores_menu);
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use crate::ui::{styles, helpers};
+use crate::ui::{styles, helpers, theme};
+use super::HudElement;
 
 #[derive(Component)]
 pub struct Hud;
@@ -7,6 +8,28 @@ pub struct Hud;
 #[derive(Component)]
 pub struct ScoreText;
 
+#[derive(Component)]
+pub struct LevelBannerText;
+
+#[derive(Component)]
+pub struct DebugText;
+
+// Prompt for the current tutorial step, empty outside of the tutorial
+#[derive(Component)]
+pub struct TutorialPromptText;
+
+// Panel in the deadspace left of the walls, holding the optional HUD elements so they never cover the playfield
+#[derive(Component)]
+pub struct HudSidePanel;
+
+// Text showing the value of an optional HUD element
+#[derive(Component)]
+pub struct HudElementText(pub HudElement);
+
+// Filled part of the level progress bar
+#[derive(Component)]
+pub struct LevelProgressFill;
+
 pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(
         
@@ -50,6 +73,71 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                 helpers::text("0", styles::TITLE_TEXT_STYLE, &asset_server)
             )
         );
+
+        // Level up banner under the score, transparent until the player levels up
+        parent.spawn(
+            (
+                LevelBannerText,
+                helpers::text("", styles::BODY_TEXT_STYLE, &asset_server),
+            )
+        );
+
+        parent.spawn(
+            (
+                TutorialPromptText,
+                helpers::text("", styles::BODY_TEXT_STYLE, &asset_server),
+            )
+        );
+
+        // Optional elements, each one is shown or hidden by the HUD settings
+        parent.spawn(
+            (
+                HudSidePanel,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        left: Val::Px(0.0),
+                        top: Val::Percent(2.0),
+                        width: Val::Px(0.0), // Set to the deadspace width by fit_hud_side_panel
+                        row_gap: Val::Percent(1.0),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    ..default()
+                },
+            )
+        )
+        .with_children(|parent| {
+            for element in HudElement::ALL {
+                if element == HudElement::LevelProgress {
+                    spawn_level_progress_bar(parent);
+                } else {
+                    parent.spawn(
+                        (
+                            element,
+                            HudElementText(element),
+                            helpers::text("", styles::SMALL_TEXT_STYLE, &asset_server),
+                        )
+                    );
+                }
+            }
+        });
+
+        // Debug overlay in the bottom left corner, hidden until toggled
+        parent.spawn(
+            (
+                DebugText,
+                helpers::text("", styles::DEBUG_TEXT_STYLE, &asset_server)
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(1.0),
+                        bottom: Val::Percent(1.0),
+                        ..default()
+                    }),
+            )
+        );
     });
 }
 
@@ -57,4 +145,37 @@ pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<Hud>>)
     if let Ok(hud_entity) = hud_query.get_single() {
         commands.entity(hud_entity).despawn_recursive();
     }
-}
\ No newline at end of file
+}
+
+fn spawn_level_progress_bar(parent: &mut ChildBuilder) {
+    parent.spawn(
+        (
+            HudElement::LevelProgress,
+            theme::ThemedBackground(theme::ThemeColor::Foreground),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(80.0),
+                    height: Val::Px(16.0),
+                    ..default()
+                },
+                ..default()
+            },
+        )
+    )
+    .with_children(|parent| {
+        parent.spawn(
+            (
+                LevelProgressFill,
+                theme::ThemedBackground(theme::ThemeColor::TextBold),
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+            )
+        );
+    });
+}
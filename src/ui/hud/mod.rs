@@ -1,20 +1,94 @@
 use bevy::prelude::*;
-use crate::AppState;
+use crate::{art, generic, locale, AppState};
+use crate::input::Action;
+use crate::settings;
 use crate::game;
+use game::{adaptive, difficulty, player, sprite_scaler, stats, tutorial};
+use crate::ui::styles;
 
 pub mod layout;
 
+const SCORE_POP_SECONDS: f32 = 0.25; // How long the score text stays enlarged after passing a platform
+const SCORE_POP_SCALE: f32 = 0.3; // How much larger the score text gets when a platform is passed
+const LEVEL_BANNER_SECONDS: f32 = 2.0; // How long the level up banner is shown for
+
+#[derive(Resource)]
+struct ScorePopTimer(Timer);
+
+#[derive(Resource)]
+struct LevelBannerTimer(Timer);
+
+// Whether the debug overlay is shown
+#[derive(Resource, Default)]
+struct DebugOverlay {
+    visible: bool,
+}
+
+// Optional elements shown on the side of the HUD, see settings::HudSettings
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudElement {
+    Level,
+    LevelProgress,
+    Speed,
+    BestScore,
+    ElapsedTime,
+}
+
+impl HudElement {
+    pub const ALL: [HudElement; 5] = [
+        HudElement::Level,
+        HudElement::LevelProgress,
+        HudElement::Speed,
+        HudElement::BestScore,
+        HudElement::ElapsedTime,
+    ];
+
+    fn enabled(&self, hud: &settings::HudSettings) -> bool {
+        match self {
+            HudElement::Level => hud.level,
+            HudElement::LevelProgress => hud.level_progress,
+            HudElement::Speed => hud.speed,
+            HudElement::BestScore => hud.best_score,
+            HudElement::ElapsedTime => hud.elapsed_time,
+        }
+    }
+}
+
+impl ScorePopTimer {
+    fn new() -> Self {
+
+        // Start with the timer finished, so the score isn't enlarged before any platforms are passed
+        let mut timer = Timer::from_seconds(SCORE_POP_SECONDS, TimerMode::Once);
+        timer.set_elapsed(timer.duration());
+        ScorePopTimer(timer)
+    }
+}
+
+impl LevelBannerTimer {
+    fn new() -> Self {
+
+        // Start finished, so the banner stays hidden until the first level up
+        let mut timer = Timer::from_seconds(LEVEL_BANNER_SECONDS, TimerMode::Once);
+        timer.set_elapsed(timer.duration());
+        LevelBannerTimer(timer)
+    }
+}
+
 pub struct HudPlugin;
 
 impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(AppState::Game), layout::spawn_hud)
+            .insert_resource(ScorePopTimer::new())
+            .insert_resource(LevelBannerTimer::new())
+            .insert_resource(DebugOverlay::default())
+            .add_systems(OnEnter(AppState::Game), (layout::spawn_hud, reset_level_banner))
 
             .add_systems(OnExit(game::GameState::GameOver), layout::despawn_hud)
             .add_systems(OnEnter(AppState::MainMenu), layout::despawn_hud)
 
-            .add_systems(Update, update_hud_score.run_if(in_state(AppState::Game)));
+            .add_systems(Update, (update_hud_score, pop_hud_score, show_level_banner, update_tutorial_prompt, update_debug_overlay).run_if(in_state(AppState::Game)))
+            .add_systems(Update, (fit_hud_side_panel, show_hud_elements, update_hud_element_texts, update_level_progress).run_if(in_state(AppState::Game)));
     }
 }
 
@@ -25,4 +99,167 @@ fn update_hud_score(
     if let Ok(mut text) = text_query.get_single_mut() {
         text.sections[0].value = game.score.to_string();
     }
-}
\ No newline at end of file
+}
+
+// Keep the side panel inside the deadspace left of the walls
+fn fit_hud_side_panel(
+    mut panel_query: Query<&mut Style, With<layout::HudSidePanel>>,
+    screen_information: Res<generic::ScreenInformation>,
+    ui_scale: Res<UiScale>,
+) {
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        let width = Val::Px(screen_information.x_deadspace / ui_scale.0 as f32); // Ui pixels are scaled by the ui scale
+        if style.width != width {
+            style.width = width;
+        }
+    }
+}
+
+// Show the HUD elements which are turned on in the settings
+fn show_hud_elements(mut element_query: Query<(&mut Style, &HudElement)>, settings: Res<settings::Settings>) {
+    for (mut style, element) in element_query.iter_mut() {
+        let display = if element.enabled(&settings.hud) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+fn update_hud_element_texts(
+    mut text_query: Query<(&mut Text, &layout::HudElementText)>,
+    player_query: Query<&player::Player>,
+    game: Res<game::Game>,
+    run_stats: Res<stats::RunStats>,
+    scale_factor: Res<sprite_scaler::ScaleFactor>,
+    locale: Res<locale::Locale>,
+) {
+    for (mut text, element_text) in text_query.iter_mut() {
+        text.sections[0].value = match element_text.0 {
+            HudElement::Level => locale.format("Level {}", &[&game.difficulty.level]),
+            HudElement::Speed => {
+                let speed = player_query.get_single().map(|player| player.speed.length()).unwrap_or(0.0);
+                format!("{:.1}m/s", speed / (art::PLAYER_SPRITE_SIZE.y * scale_factor.current)) // A plane length counts as a meter, the same as the run depth
+            },
+            HudElement::BestScore => {
+                if run_stats.beat_best(game.score) {
+                    locale.get("New best!")
+                } else {
+                    locale.format("Best {} ({})", &[&run_stats.previous_best, &(game.score - run_stats.previous_best)])
+                }
+            },
+            HudElement::ElapsedTime => stats::time_text(run_stats.time_survived),
+            HudElement::LevelProgress => String::new(),
+        };
+    }
+}
+
+fn update_level_progress(
+    mut fill_query: Query<&mut Style, With<layout::LevelProgressFill>>,
+    game: Res<game::Game>,
+    difficulty_curves: Res<difficulty::DifficultyCurves>,
+) {
+    if let Ok(mut style) = fill_query.get_single_mut() {
+        style.width = Val::Percent(difficulty_curves.level_progress(game.score) * 100.0);
+    }
+}
+
+// Enlarge the score text the moment a platform is passed, then shrink it back down
+// The score doesn't pop with reduced motion
+fn pop_hud_score(
+    mut text_query: Query<&mut Text, With<layout::ScoreText>>,
+    mut platform_passed: EventReader<game::platforms::PlatformPassed>,
+    mut pop_timer: ResMut<ScorePopTimer>,
+    settings: Res<settings::Settings>,
+    time: Res<Time>,
+) {
+    for _ in platform_passed.read() {
+        pop_timer.0.reset();
+    }
+
+    pop_timer.0.tick(time.delta());
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let pop = if settings.reduced_motion {
+            0.0
+        } else {
+            SCORE_POP_SCALE * pop_timer.0.percent_left()
+        };
+        text.sections[0].style.font_size = styles::TITLE_TEXT_STYLE.size * (1.0 + pop);
+    }
+}
+
+fn reset_level_banner(mut banner_timer: ResMut<LevelBannerTimer>) {
+    *banner_timer = LevelBannerTimer::new();
+}
+
+// Show the new level under the score when the player levels up, fading out over LEVEL_BANNER_SECONDS
+fn show_level_banner(
+    mut text_query: Query<&mut Text, With<layout::LevelBannerText>>,
+    mut level_up: EventReader<game::LevelUp>,
+    mut banner_timer: ResMut<LevelBannerTimer>,
+    locale: Res<locale::Locale>,
+    time: Res<Time>,
+) {
+    let mut new_level = None;
+    for event in level_up.read() {
+        new_level = Some(event.level);
+        banner_timer.0.reset();
+    }
+
+    banner_timer.0.tick(time.delta());
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        if let Some(level) = new_level {
+            text.sections[0].value = locale.format("Level {}", &[&level]);
+        }
+        text.sections[0].style.color.set_a(banner_timer.0.percent_left());
+    }
+}
+
+// Show what the current tutorial step asks the player to do
+fn update_tutorial_prompt(
+    mut text_query: Query<&mut Text, With<layout::TutorialPromptText>>,
+    tutorial: Res<tutorial::Tutorial>,
+    settings: Res<settings::Settings>,
+    locale: Res<locale::Locale>,
+) {
+    if !tutorial.is_changed() && !settings.is_changed() && !locale.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = tutorial.prompt(&settings, &locale);
+    }
+}
+
+// Toggle the debug overlay with the debug action, and show the current adaptive difficulty offset on it
+fn update_debug_overlay(
+    actions: Res<Input<Action>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+    mut text_query: Query<(&mut Text, &mut Visibility), With<layout::DebugText>>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+) {
+    if actions.just_pressed(Action::ToggleDebug) {
+        debug_overlay.visible = !debug_overlay.visible;
+    }
+
+    if let Ok((mut text, mut visibility)) = text_query.get_single_mut() {
+        *visibility = if debug_overlay.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        let adaptive_text = if adaptive_difficulty.enabled {
+            format!("{:+.2} (range {:+.2} to {:+.2})", adaptive_difficulty.offset, adaptive::OFFSET_RANGE.min, adaptive::OFFSET_RANGE.max)
+        } else {
+            "off".to_string()
+        };
+        text.sections[0].value = format!("Adaptive offset: {}", adaptive_text);
+    }
+}
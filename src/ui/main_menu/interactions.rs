@@ -1,20 +1,111 @@
 use bevy::prelude::*;
-use crate::AppState;
+use crate::{AppState, game, ui, locale};
+use game::{adaptive, difficulty, tutorial};
 use super::layout;
 
 // Start the game when the play button is pressed
+// The tutorial is only played if it hasn't been completed yet
 pub fn play_button_interactions(
     button_query: Query<&Interaction, (Changed<Interaction>, With<layout::PlayButton>)>,
+    mut tutorial: ResMut<tutorial::Tutorial>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
     if let Ok(interaction) = button_query.get_single() {
         match interaction {
-            Interaction::Pressed => next_state.set(AppState::GameSetup),
+            Interaction::Pressed => {
+                tutorial.replay = false;
+                next_state.set(AppState::GameSetup);
+            },
             _ => (), 
         }
     }
 }
 
+// Start a run with the tutorial, even when it has been completed before
+pub fn tutorial_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::TutorialButton>)>,
+    mut tutorial: ResMut<tutorial::Tutorial>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => {
+                tutorial.replay = true;
+                next_state.set(AppState::GameSetup);
+            },
+            _ => (),
+        }
+    }
+}
+
+// Cycle through difficulty presets when the preset button is pressed
+pub fn preset_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::PresetButton>)>,
+    mut preset_text_query: Query<&mut Text, With<layout::PresetButtonText>>,
+    mut preset: ResMut<difficulty::DifficultyPreset>,
+    locale: Res<locale::Locale>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => {
+                *preset = preset.next();
+
+                if let Ok(mut text) = preset_text_query.get_single_mut() {
+                    text.sections[0].value = layout::preset_button_text(*preset, &locale);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+// Toggle adaptive difficulty when the adaptive button is pressed
+pub fn adaptive_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::AdaptiveButton>)>,
+    mut text_query: Query<&mut Text, With<layout::AdaptiveButtonText>>,
+    mut adaptive_difficulty: ResMut<adaptive::AdaptiveDifficulty>,
+    locale: Res<locale::Locale>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => {
+                adaptive_difficulty.enabled = !adaptive_difficulty.enabled;
+
+                if let Ok(mut text) = text_query.get_single_mut() {
+                    text.sections[0].value = layout::adaptive_button_text(adaptive_difficulty.enabled, &locale);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+// Open the high score table
+pub fn high_scores_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::HighScoresButton>)>,
+    mut next_sub_menu: ResMut<NextState<ui::SubMenu>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => next_sub_menu.set(ui::SubMenu::HighScores),
+            _ => (),
+        }
+    }
+}
+
+// Open the lifetime statistics
+pub fn statistics_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<layout::StatisticsButton>)>,
+    mut next_sub_menu: ResMut<NextState<ui::SubMenu>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => next_sub_menu.set(ui::SubMenu::Statistics),
+            _ => (),
+        }
+    }
+}
+
 // Send exit app event when the quit button is pressed
 pub fn quit_button_interactions(
     button_query: Query<&Interaction, (Changed<Interaction>, With<layout::QuitButton>)>,
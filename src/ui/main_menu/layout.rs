@@ -1,6 +1,7 @@
 use bevy::prelude::*;
-use crate::ui;
-use ui::{styles, helpers};
+use crate::{ui, game, save, locale, settings};
+use ui::{styles, helpers, theme};
+use game::{adaptive, difficulty};
 
 #[derive(Component)]
 pub struct MainMenu;
@@ -12,12 +13,47 @@ pub struct PlayButton;
 #[derive(Component)]
 pub struct QuitButton;
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+pub struct PresetButton;
+
+#[derive(Component)]
+pub struct PresetButtonText;
+
+#[derive(Component)]
+pub struct BestScoreText;
+
+#[derive(Component)]
+pub struct AdaptiveButton;
+
+#[derive(Component)]
+pub struct AdaptiveButtonText;
+
+#[derive(Component)]
+pub struct HighScoresButton;
+
+#[derive(Component)]
+pub struct TutorialButton;
+
+#[derive(Component)]
+pub struct StatisticsButton;
+
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    locale: Res<locale::Locale>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+    preset: Res<difficulty::DifficultyPreset>,
+    save_data: Res<save::SaveData>,
+    settings: Res<settings::Settings>,
+) {
+    let mode = save::RunMode::current(*preset, &adaptive_difficulty, settings.control_scheme);
+
     commands.spawn(
 
         // Main menu background, and parent node
         (
             MainMenu,
+            theme::ThemedBackground(theme::ThemeColor::Background),
 
             NodeBundle {
                 style: Style {
@@ -31,7 +67,6 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     row_gap: Val::Percent(1.0),
                     ..default()
                 },
-                background_color: Color::hex(styles::BACKGROUND_HEX).unwrap().into(),
                 ..default()
             }
         )
@@ -46,7 +81,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     margin: UiRect {
-                        bottom: Val::Percent(10.0),
+                        bottom: Val::Percent(2.0),
                         ..default()
                     },
                     ..default()
@@ -55,7 +90,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
             }
         ).with_children(|parent| {
             parent.spawn(
-                helpers::text("Rust Paper Plane", styles::TITLE_TEXT_STYLE, &asset_server)
+                helpers::localized_text("Rust Paper Plane", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
             );
         });
         
@@ -69,7 +104,119 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Play", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Play", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
+            );
+        });
+
+        // Tutorial button, plays the tutorial again
+        parent.spawn(
+            (
+                ui::GenericButton,
+                TutorialButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Tutorial", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Difficulty preset button, cycles through the presets
+        parent.spawn(
+            (
+                ui::GenericButton,
+                PresetButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                (
+                    PresetButtonText,
+                    helpers::text(&preset_button_text(*preset, &locale), styles::BUTTON_STYLE.text_style.unwrap(), &asset_server),
+                )
+            );
+        });
+
+        // Best score for the selected preset, adaptive difficulty and control scheme
+        parent.spawn(
+            (
+                BestScoreText,
+                helpers::text(&best_score_text(mode, &save_data, &locale), styles::BODY_TEXT_STYLE, &asset_server),
+            )
+        );
+
+        // Adaptive difficulty toggle button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                AdaptiveButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                (
+                    AdaptiveButtonText,
+                    helpers::text(&adaptive_button_text(adaptive_difficulty.enabled, &locale), styles::BUTTON_STYLE.text_style.unwrap(), &asset_server),
+                )
+            );
+        });
+
+        // High score table button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                HighScoresButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("High Scores", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Lifetime statistics button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                StatisticsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Statistics", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Settings screen button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SettingsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Settings", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Controls screen button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::ControlsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Controls", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
             );
         });
 
@@ -83,7 +230,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Quit", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Quit", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
             );
         });
 
@@ -94,4 +241,39 @@ pub fn despawn_main_menu(mut commands: Commands, main_menu_query: Query<Entity,
     if let Ok(main_menu_entity) = main_menu_query.get_single() {
         commands.entity(main_menu_entity).despawn_recursive();
     }
-}
\ No newline at end of file
+}
+
+pub fn adaptive_button_text(enabled: bool, locale: &locale::Locale) -> String {
+    if enabled {
+        locale.get("Adaptive: On")
+    } else {
+        locale.get("Adaptive: Off")
+    }
+}
+
+pub fn preset_button_text(preset: difficulty::DifficultyPreset, locale: &locale::Locale) -> String {
+    locale.format("Difficulty: {}", &[&locale.get(preset.name())])
+}
+
+// Keep the best score text showing the mode the next run would be played in
+pub fn update_best_score_text(
+    mut text_query: Query<&mut Text, With<BestScoreText>>,
+    preset: Res<difficulty::DifficultyPreset>,
+    adaptive_difficulty: Res<adaptive::AdaptiveDifficulty>,
+    settings: Res<settings::Settings>,
+    save_data: Res<save::SaveData>,
+    locale: Res<locale::Locale>,
+) {
+    if !preset.is_changed() && !adaptive_difficulty.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let mode = save::RunMode::current(*preset, &adaptive_difficulty, settings.control_scheme);
+        text.sections[0].value = best_score_text(mode, &save_data, &locale);
+    }
+}
+
+pub fn best_score_text(mode: save::RunMode, save_data: &save::SaveData, locale: &locale::Locale) -> String {
+    locale.format("{} best: {}", &[&mode.name(locale), &save_data.best_score(mode)])
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::AppState;
+use crate::ui::SubMenu;
 
 pub mod layout;
 pub mod interactions;
@@ -11,6 +12,16 @@ impl Plugin for MainMenuPlugin {
         app
             .add_systems(OnEnter(AppState::MainMenu), layout::spawn_main_menu)
             .add_systems(OnExit(AppState::MainMenu), layout::despawn_main_menu)
-            .add_systems(Update, (interactions::play_button_interactions, interactions::quit_button_interactions).run_if(in_state(AppState::MainMenu)));
+
+            // Hide the main menu while a sub menu opened from it is open
+            .add_systems(OnEnter(SubMenu::Controls), layout::despawn_main_menu)
+            .add_systems(OnExit(SubMenu::Controls), layout::spawn_main_menu.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnEnter(SubMenu::Settings), layout::despawn_main_menu)
+            .add_systems(OnExit(SubMenu::Settings), layout::spawn_main_menu.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnEnter(SubMenu::HighScores), layout::despawn_main_menu)
+            .add_systems(OnExit(SubMenu::HighScores), layout::spawn_main_menu.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnEnter(SubMenu::Statistics), layout::despawn_main_menu)
+            .add_systems(OnExit(SubMenu::Statistics), layout::spawn_main_menu.run_if(in_state(AppState::MainMenu)))
+            .add_systems(Update, (interactions::play_button_interactions, interactions::tutorial_button_interactions, interactions::preset_button_interactions, interactions::adaptive_button_interactions, interactions::high_scores_button_interactions, interactions::statistics_button_interactions, interactions::quit_button_interactions, layout::update_best_score_text).run_if(in_state(AppState::MainMenu)));
     }
 }
\ No newline at end of file
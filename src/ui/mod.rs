@@ -5,9 +5,16 @@ pub mod pause_menu;
 pub mod main_menu;
 pub mod hud;
 pub mod game_over_menu;
+pub mod focus;
+pub mod controls_menu;
+pub mod settings_menu;
+pub mod high_scores_menu;
+pub mod statistics_menu;
+pub mod countdown;
 
 pub mod helpers;
 pub mod styles;
+pub mod theme;
 
 #[derive(Component)]
 pub struct GenericButton;
@@ -15,26 +22,47 @@ pub struct GenericButton;
 #[derive(Component)]
 pub struct GenericBackButton;
 
+// Opens the controls screen, used by the main and pause menus
+#[derive(Component)]
+pub struct ControlsButton;
+
+// Opens the settings screen, used by the main and pause menus
+#[derive(Component)]
+pub struct SettingsButton;
+
+// Closes the open sub menu, going back to the menu it was opened from
+#[derive(Component)]
+pub struct SubMenuBackButton;
+
+// Screens opened from the main or pause menu, the menu they were opened from is hidden while they are open
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SubMenu {
+    #[default]
+    None,
+    Controls,
+    Settings,
+    HighScores,
+    Statistics,
+}
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app
-        .add_plugins((main_menu::MainMenuPlugin, hud::HudPlugin, pause_menu::PauseMenuPlugin, game_over_menu::GameOverMenuPlugin))
-        .add_systems(Update, (button_interactions, back_button_interactions));
+        .add_state::<SubMenu>()
+        .add_plugins((controls_menu::ControlsMenuPlugin, settings_menu::SettingsMenuPlugin, high_scores_menu::HighScoresMenuPlugin, statistics_menu::StatisticsMenuPlugin, main_menu::MainMenuPlugin, hud::HudPlugin, pause_menu::PauseMenuPlugin, game_over_menu::GameOverMenuPlugin, focus::MenuFocusPlugin, theme::UiThemePlugin, countdown::CountdownPlugin))
+        .add_systems(Update, (button_interactions, back_button_interactions, controls_button_interactions, settings_button_interactions, sub_menu_back_button_interactions));
     }
 }
 
 // Changes button colors when the user interacts with it
 pub fn button_interactions(
-    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    ui_theme: Res<theme::UiTheme>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
-        match interaction {
-            Interaction::Pressed => *background_color = Color::hex(styles::BUTTON_PRESSED_HEX).unwrap().into(),
-            Interaction::Hovered => *background_color = Color::hex(styles::BUTTON_HOVER_HEX).unwrap().into(),
-            Interaction::None => *background_color = Color::hex(styles::BUTTON_DEFAULT_HEX).unwrap().into(),
-        }
+        *background_color = ui_theme.button_color(*interaction).into();
     }
 }
 
@@ -54,4 +82,43 @@ pub fn back_button_interactions(
             _ => (), 
         }
     }
-}
\ No newline at end of file
+}
+
+// Open the controls screen
+pub fn controls_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<ControlsButton>)>,
+    mut next_sub_menu: ResMut<NextState<SubMenu>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => next_sub_menu.set(SubMenu::Controls),
+            _ => (),
+        }
+    }
+}
+
+// Open the settings screen
+pub fn settings_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut next_sub_menu: ResMut<NextState<SubMenu>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => next_sub_menu.set(SubMenu::Settings),
+            _ => (),
+        }
+    }
+}
+
+// Close the open sub menu
+pub fn sub_menu_back_button_interactions(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<SubMenuBackButton>)>,
+    mut next_sub_menu: ResMut<NextState<SubMenu>>,
+) {
+    if let Ok(interaction) = button_query.get_single() {
+        match interaction {
+            Interaction::Pressed => next_sub_menu.set(SubMenu::None),
+            _ => (),
+        }
+    }
+}
@@ -1,16 +1,17 @@
 use bevy::prelude::*;
 
-use crate::game::GameState;
+use crate::{game::GameState, settings, ui::countdown};
 use super::layout;
 
-// Unpause the game
+// Unpause the game, through the countdown when it is turned on
 pub fn resume_button_interactions(
     button_query: Query<&Interaction, (Changed<Interaction>, With<layout::ResumeButton>)>,
+    settings: Res<settings::Settings>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     if let Ok(interaction) = button_query.get_single() {
         match interaction {
-            Interaction::Pressed => next_state.set(GameState::Running),
+            Interaction::Pressed => next_state.set(countdown::resume_state(&settings)),
             _ => (), 
         }
     }
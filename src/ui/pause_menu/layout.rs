@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use crate::ui;
-use ui::{helpers, styles};
+use crate::{ui, locale};
+use ui::{helpers, styles, theme};
 
 #[derive(Component)]
 pub struct PauseMenu;
@@ -9,11 +9,12 @@ pub struct PauseMenu;
 #[derive(Component)]
 pub struct ResumeButton;
 
-pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>, locale: Res<locale::Locale>) {
     commands.spawn(
 
         (
             PauseMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
 
             NodeBundle {
                 style: Style {
@@ -28,7 +29,6 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
 
                     ..default()
                 },
-                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
                 ..default()
             }
         )
@@ -52,7 +52,7 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
             }
         ).with_children(|parent| {
             parent.spawn(
-                helpers::text("Paused", styles::TITLE_TEXT_STYLE, &asset_server)
+                helpers::localized_text("Paused", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
             );
         });
         
@@ -66,7 +66,35 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Resume", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Resume", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
+            );
+        });
+
+        // Settings screen button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SettingsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Settings", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+
+        // Controls screen button
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::ControlsButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )   
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Controls", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
             );
         });
 
@@ -80,7 +108,7 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         )
         .with_children(|parent| {
             parent.spawn(
-                helpers::text("Main Menu", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server) // Play button text
+                helpers::localized_text("Main Menu", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale) // Play button text
             );
         });
 
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
+use bevy::input::gamepad::GamepadConnectionEvent;
 
-use crate::{AppState, game::GameState};
+use crate::{AppState, game::GameState, input::Action, settings};
+use crate::ui::countdown;
+use crate::ui::SubMenu;
 
 pub mod layout;
 pub mod interactions;
@@ -12,23 +15,32 @@ impl Plugin for PauseMenuPlugin {
         app
             .add_systems(OnEnter(GameState::Paused), layout::spawn_pause_menu)
             .add_systems(OnExit(GameState::Paused), layout::despawn_pause_menu)
-            .add_systems(Update, (change_pause_state, interactions::resume_button_interactions).run_if(in_state(AppState::Game)));
+
+            // Hide the pause menu while a sub menu opened from it is open
+            .add_systems(OnEnter(SubMenu::Controls), layout::despawn_pause_menu)
+            .add_systems(OnExit(SubMenu::Controls), layout::spawn_pause_menu.run_if(in_state(GameState::Paused)).run_if(in_state(AppState::Game)))
+            .add_systems(OnEnter(SubMenu::Settings), layout::despawn_pause_menu)
+            .add_systems(OnExit(SubMenu::Settings), layout::spawn_pause_menu.run_if(in_state(GameState::Paused)).run_if(in_state(AppState::Game)))
+
+            .add_systems(Update, change_pause_state.run_if(in_state(AppState::Game)).run_if(in_state(SubMenu::None)))
+            .add_systems(Update, (pause_on_gamepad_disconnect, interactions::resume_button_interactions).run_if(in_state(AppState::Game)));
     }
 }
 
-// Toggle pause menu when the escape button is pressed
+// Toggle pause menu when the pause action is pressed
 fn change_pause_state(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
     game_state: Res<State<GameState>>,
     app_state: Res<State<AppState>>,
+    settings: Res<settings::Settings>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(Action::Pause) {
 
 
         let state = match game_state.get() {
-            GameState::Paused => GameState::Running,
-            GameState::Running => GameState::Paused,
+            GameState::Paused => countdown::resume_state(&settings),
+            GameState::Running | GameState::Countdown => GameState::Paused,
             GameState::GameOver => GameState::GameOver,
         };
 
@@ -40,4 +52,18 @@ fn change_pause_state(
     if app_state.get() != &AppState::Game {
         next_state.set(GameState::Running);
     }
-}
\ No newline at end of file
+}
+
+// Pause when a gamepad disconnects, so the player doesn't crash while reconnecting it
+fn pause_on_gamepad_disconnect(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in connection_events.read() {
+        if event.disconnected() && matches!(game_state.get(), GameState::Running | GameState::Countdown) {
+            info!("Gamepad {:?} disconnected, pausing", event.gamepad);
+            next_state.set(GameState::Paused);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::settings;
+use super::layout;
+
+// Move a setting on to its next value when its button is pressed, and save the settings
+pub fn setting_button_interactions(
+    button_query: Query<(&Interaction, &layout::SettingButton), Changed<Interaction>>,
+    mut settings: ResMut<settings::Settings>,
+) {
+    for (interaction, setting_button) in button_query.iter() {
+        match interaction {
+            Interaction::Pressed => {
+                setting_button.0.change(&mut settings);
+                settings.save();
+            },
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::{ui, settings, locale, AppState};
+use ui::{helpers, styles, theme};
+use super::SettingRow;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+#[derive(Component)]
+pub struct SettingButton(pub SettingRow);
+
+#[derive(Component)]
+pub struct SettingButtonText(pub SettingRow);
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    locale: Res<locale::Locale>,
+    settings: Res<settings::Settings>,
+    app_state: Res<State<AppState>>,
+) {
+    commands.spawn(
+
+        (
+            SettingsMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
+
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+
+                    row_gap: Val::Percent(0.3),
+
+                    ..default()
+                },
+                ..default()
+            }
+        )
+    )
+
+    .with_children(|parent| {
+
+        // Title
+        parent.spawn(
+            helpers::localized_text("Settings", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
+        );
+
+        // One row for every setting, with its name and a button showing its value
+        for setting in SettingRow::ALL {
+            parent.spawn(
+                NodeBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(3.6),
+                        column_gap: Val::Percent(1.0),
+                        ..default()
+                    },
+                    ..default()
+                }
+            ).with_children(|parent| {
+                parent.spawn(
+                    (
+                        locale::LocalizedText(setting.label()),
+                        helpers::text(&locale.get(setting.label()), styles::SMALL_TEXT_STYLE, &asset_server)
+                            .with_style(Style {
+                                width: Val::Percent(20.0),
+                                ..default()
+                            }),
+                    )
+                );
+
+                // Settings which would change the run, shown without a button while one is in progress
+                if setting.locked_during_run() && app_state.get() == &AppState::Game {
+                    parent.spawn(
+                        helpers::text(&locale.format("{} (locked during a run)", &[&setting.value_text(&settings, &locale)]), styles::SMALL_TEXT_STYLE, &asset_server)
+                            .with_style(Style {
+                                width: styles::LIST_BUTTON_STYLE.width,
+                                ..default()
+                            })
+                    );
+                    return;
+                }
+
+                parent.spawn(
+                    (
+                        ui::GenericButton,
+                        SettingButton(setting),
+                        helpers::button(styles::LIST_BUTTON_STYLE),
+                    )
+                )
+                .with_children(|parent| {
+                    parent.spawn(
+                        (
+                            SettingButtonText(setting),
+                            helpers::text(&setting.value_text(&settings, &locale), styles::LIST_BUTTON_STYLE.text_style.unwrap(), &asset_server),
+                        )
+                    );
+                });
+            });
+        }
+
+        // Back button, returns to the menu the settings screen was opened from
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SubMenuBackButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Back", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+    });
+}
+
+pub fn despawn_settings_menu(mut commands: Commands, menu_query: Query<Entity, With<SettingsMenu>>) {
+    if let Ok(menu_entity) = menu_query.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
+
+pub fn update_setting_texts(settings: Res<settings::Settings>, locale: Res<locale::Locale>, mut text_query: Query<(&mut Text, &SettingButtonText)>) {
+    if !settings.is_changed() && !locale.is_changed() {
+        return;
+    }
+
+    for (mut text, setting_text) in text_query.iter_mut() {
+        text.sections[0].value = setting_text.0.value_text(&settings, &locale);
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::settings::{self, Settings};
+use crate::locale::Locale;
+use super::SubMenu;
+
+pub mod layout;
+pub mod interactions;
+
+// A setting shown on the settings screen, pressing its button moves it on to the next value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingRow {
+    WindowMode,
+    Resolution,
+    Vsync,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiScale,
+    ReducedMotion,
+    Countdown,
+    Steering,
+    Flight,
+    HudLevel,
+    HudLevelProgress,
+    HudSpeed,
+    HudBestScore,
+    HudElapsedTime,
+    Language,
+    Theme,
+}
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(SubMenu::Settings), layout::spawn_settings_menu)
+            .add_systems(OnExit(SubMenu::Settings), layout::despawn_settings_menu)
+            .add_systems(Update, (interactions::setting_button_interactions, layout::update_setting_texts).chain().run_if(in_state(SubMenu::Settings)));
+    }
+}
+
+impl SettingRow {
+    pub const ALL: [SettingRow; 18] = [
+        SettingRow::Language,
+        SettingRow::Theme,
+        SettingRow::WindowMode,
+        SettingRow::Resolution,
+        SettingRow::Vsync,
+        SettingRow::MasterVolume,
+        SettingRow::MusicVolume,
+        SettingRow::SfxVolume,
+        SettingRow::UiScale,
+        SettingRow::ReducedMotion,
+        SettingRow::Countdown,
+        SettingRow::Steering,
+        SettingRow::Flight,
+        SettingRow::HudLevel,
+        SettingRow::HudLevelProgress,
+        SettingRow::HudSpeed,
+        SettingRow::HudBestScore,
+        SettingRow::HudElapsedTime,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingRow::WindowMode => "Window mode",
+            SettingRow::Resolution => "Resolution",
+            SettingRow::Vsync => "Vsync",
+            SettingRow::MasterVolume => "Master volume",
+            SettingRow::MusicVolume => "Music volume",
+            SettingRow::SfxVolume => "Sound effects",
+            SettingRow::UiScale => "UI scale",
+            SettingRow::ReducedMotion => "Reduced motion",
+            SettingRow::Countdown => "Countdown",
+            SettingRow::Steering => "Steering",
+            SettingRow::Flight => "Flight model",
+            SettingRow::HudLevel => "HUD level",
+            SettingRow::HudLevelProgress => "HUD level progress",
+            SettingRow::HudSpeed => "HUD speed",
+            SettingRow::HudBestScore => "HUD best score",
+            SettingRow::HudElapsedTime => "HUD time",
+            SettingRow::Language => "Language",
+            SettingRow::Theme => "Theme",
+        }
+    }
+
+    pub fn value_text(&self, settings: &Settings, locale: &Locale) -> String {
+        match self {
+            SettingRow::WindowMode => locale.get(settings.window_mode.name()),
+            SettingRow::Resolution => format!("{} x {}", settings.resolution[0], settings.resolution[1]),
+            SettingRow::Vsync => on_off_text(settings.vsync, locale),
+            SettingRow::MasterVolume => volume_text(settings.master_volume),
+            SettingRow::MusicVolume => volume_text(settings.music_volume),
+            SettingRow::SfxVolume => volume_text(settings.sfx_volume),
+            SettingRow::UiScale => format!("{:.0}%", settings.ui_scale * 100.0),
+            SettingRow::ReducedMotion => on_off_text(settings.reduced_motion, locale),
+            SettingRow::Countdown => on_off_text(settings.countdown, locale),
+            SettingRow::Steering => locale.get(settings.control_scheme.name()),
+            SettingRow::Flight => locale.get(settings.flight_model.name()),
+            SettingRow::HudLevel => on_off_text(settings.hud.level, locale),
+            SettingRow::HudLevelProgress => on_off_text(settings.hud.level_progress, locale),
+            SettingRow::HudSpeed => on_off_text(settings.hud.speed, locale),
+            SettingRow::HudBestScore => on_off_text(settings.hud.best_score, locale),
+            SettingRow::HudElapsedTime => on_off_text(settings.hud.elapsed_time, locale),
+            SettingRow::Language => settings.language.name().to_string(), // Always shown in its own language
+            SettingRow::Theme => locale.get(settings.ui_palette.name()),
+        }
+    }
+
+    // The control scheme changes the difficulty when a run starts, so it can't be changed part way through a run
+    pub fn locked_during_run(&self) -> bool {
+        matches!(self, SettingRow::Steering)
+    }
+
+    pub fn change(&self, settings: &mut Settings) {
+        match self {
+            SettingRow::WindowMode => settings.window_mode = settings.window_mode.next(),
+            SettingRow::Resolution => settings.resolution = settings::next_option(&settings::RESOLUTIONS, settings.resolution),
+            SettingRow::Vsync => settings.vsync = !settings.vsync,
+            SettingRow::MasterVolume => settings.master_volume = settings::next_volume(settings.master_volume),
+            SettingRow::MusicVolume => settings.music_volume = settings::next_volume(settings.music_volume),
+            SettingRow::SfxVolume => settings.sfx_volume = settings::next_volume(settings.sfx_volume),
+            SettingRow::UiScale => settings.ui_scale = settings::next_option(&settings::UI_SCALES, settings.ui_scale),
+            SettingRow::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            SettingRow::Countdown => settings.countdown = !settings.countdown,
+            SettingRow::Steering => settings.control_scheme = settings.control_scheme.next(),
+            SettingRow::Flight => settings.flight_model = settings.flight_model.next(),
+            SettingRow::HudLevel => settings.hud.level = !settings.hud.level,
+            SettingRow::HudLevelProgress => settings.hud.level_progress = !settings.hud.level_progress,
+            SettingRow::HudSpeed => settings.hud.speed = !settings.hud.speed,
+            SettingRow::HudBestScore => settings.hud.best_score = !settings.hud.best_score,
+            SettingRow::HudElapsedTime => settings.hud.elapsed_time = !settings.hud.elapsed_time,
+            SettingRow::Language => settings.language = settings.language.next(),
+            SettingRow::Theme => settings.ui_palette = settings.ui_palette.next(),
+        }
+    }
+}
+
+fn on_off_text(on: bool, locale: &Locale) -> String {
+    if on {
+        locale.get("On")
+    } else {
+        locale.get("Off")
+    }
+}
+
+fn volume_text(volume: f32) -> String {
+    format!("{:.0}%", volume * 100.0)
+}
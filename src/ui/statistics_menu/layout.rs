@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+
+use crate::{ui, save, locale, game};
+use ui::{helpers, styles, theme};
+use game::{difficulty::DifficultyPreset, stats};
+use super::bar_fraction;
+
+#[derive(Component)]
+pub struct StatisticsMenu;
+
+const ROW_HEIGHT: f32 = 3.5;
+
+pub fn spawn_statistics_menu(mut commands: Commands, asset_server: Res<AssetServer>, locale: Res<locale::Locale>, save_data: Res<save::SaveData>) {
+    let lifetime = &save_data.lifetime;
+
+    commands.spawn(
+
+        (
+            StatisticsMenu,
+            theme::ThemedBackground(theme::ThemeColor::Overlay),
+
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+
+                    row_gap: Val::Percent(0.5),
+
+                    ..default()
+                },
+                ..default()
+            }
+        )
+    )
+
+    .with_children(|parent| {
+
+        // Title
+        parent.spawn(
+            helpers::localized_text("Statistics", styles::TITLE_TEXT_STYLE, &asset_server, &locale)
+        );
+
+        let totals = [
+            ("Total runs", lifetime.total_runs.to_string()),
+            ("Platforms passed", lifetime.total_platforms_passed.to_string()),
+            ("Play time", stats::time_text(lifetime.total_play_time)),
+            ("Average score", format!("{:.1}", lifetime.average_score())),
+            ("Longest run", stats::time_text(lifetime.longest_run)),
+        ];
+        for (label, value) in totals {
+            spawn_value_row(parent, &locale.get(label), &value, &asset_server);
+        }
+
+        // Best score for each preset, played without adaptive difficulty or an assisted control scheme
+        parent.spawn(
+            helpers::localized_text("Best score by mode", styles::BODY_TEXT_STYLE, &asset_server, &locale)
+        );
+        let best_scores = [DifficultyPreset::Easy, DifficultyPreset::Normal, DifficultyPreset::Hard]
+            .map(|preset| (locale.get(preset.name()), save_data.best_score(save::RunMode::plain(preset)) as f32));
+        spawn_bar_chart(parent, &best_scores, &asset_server);
+
+        // How runs ended
+        parent.spawn(
+            helpers::localized_text("Deaths", styles::BODY_TEXT_STYLE, &asset_server, &locale)
+        );
+        let deaths = &lifetime.deaths;
+        let death_counts = [
+            ("Left wall", deaths.wall_left),
+            ("Right wall", deaths.wall_right),
+            ("Platform on the left", deaths.platform_left),
+            ("Platform on the right", deaths.platform_right),
+        ].map(|(label, count)| (locale.get(label), count as f32));
+        spawn_bar_chart(parent, &death_counts, &asset_server);
+
+        // Back button, returns to the main menu
+        parent.spawn(
+            (
+                ui::GenericButton,
+                ui::SubMenuBackButton,
+                helpers::button(styles::BUTTON_STYLE),
+            )
+        )
+        .with_children(|parent| {
+            parent.spawn(
+                helpers::localized_text("Back", styles::BUTTON_STYLE.text_style.unwrap(), &asset_server, &locale)
+            );
+        });
+    });
+}
+
+pub fn despawn_statistics_menu(mut commands: Commands, menu_query: Query<Entity, With<StatisticsMenu>>) {
+    if let Ok(menu_entity) = menu_query.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
+
+// Label on the left and value on the right
+fn spawn_value_row(parent: &mut ChildBuilder, label: &str, value: &str, asset_server: &AssetServer) {
+    parent.spawn(
+        NodeBundle {
+            style: Style {
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                width: Val::Percent(40.0),
+                height: Val::Percent(ROW_HEIGHT),
+                ..default()
+            },
+            ..default()
+        }
+    ).with_children(|parent| {
+        parent.spawn(helpers::text(label, styles::SMALL_TEXT_STYLE, asset_server));
+        parent.spawn(helpers::text(value, styles::SMALL_TEXT_STYLE, asset_server));
+    });
+}
+
+// One bar for each value, the longest value fills its bar
+fn spawn_bar_chart(parent: &mut ChildBuilder, bars: &[(String, f32)], asset_server: &AssetServer) {
+    let max = bars.iter().map(|(_, value)| *value).fold(0.0, f32::max);
+
+    for (label, value) in bars {
+        parent.spawn(
+            NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Percent(60.0),
+                    height: Val::Percent(ROW_HEIGHT),
+                    column_gap: Val::Percent(1.0),
+                    ..default()
+                },
+                ..default()
+            }
+        ).with_children(|parent| {
+            parent.spawn(
+                helpers::text(label, styles::SMALL_TEXT_STYLE, asset_server)
+                    .with_style(Style {
+                        width: Val::Percent(35.0),
+                        ..default()
+                    })
+            );
+
+            // Bar background, with the filled part inside it
+            parent.spawn(
+                (
+                    theme::ThemedBackground(theme::ThemeColor::Foreground),
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(50.0),
+                            height: Val::Percent(60.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                )
+            ).with_children(|parent| {
+                parent.spawn(
+                    (
+                        theme::ThemedBackground(theme::ThemeColor::TextBold),
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(bar_fraction(*value, max) * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    )
+                );
+            });
+
+            parent.spawn(
+                helpers::text(&value.to_string(), styles::SMALL_TEXT_STYLE, asset_server)
+                    .with_style(Style {
+                        width: Val::Percent(10.0),
+                        ..default()
+                    })
+            );
+        });
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use super::SubMenu;
+
+pub mod layout;
+
+pub struct StatisticsMenuPlugin;
+
+impl Plugin for StatisticsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(SubMenu::Statistics), layout::spawn_statistics_menu)
+            .add_systems(OnExit(SubMenu::Statistics), layout::despawn_statistics_menu);
+    }
+}
+
+// How much of a bar chart row a value fills, from 0 to 1
+pub fn bar_fraction(value: f32, max: f32) -> f32 {
+    if max <= 0.0 {
+        return 0.0;
+    }
+    (value / max).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fraction() {
+        assert_eq!(bar_fraction(5.0, 10.0), 0.5);
+        assert_eq!(bar_fraction(10.0, 10.0), 1.0);
+        assert_eq!(bar_fraction(3.0, 0.0), 0.0); // Nothing recorded yet
+    }
+}
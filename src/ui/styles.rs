@@ -1,18 +1,11 @@
 use bevy::prelude::*;
+use super::theme::ThemeColor;
 
-// Color scheme using https://lospec.com/palette-list/sweetie-16 color palette
-pub const BACKGROUND_HEX: &'static str = "1a1c2c";
-pub const FOREGROUND_HEX: &'static str = "333c57";
-pub const TEXT_HEX: &'static str = "f4f4f4";
-pub const TEXT_BOLD_HEX: &'static str = "94b0c2";
-
-pub const BUTTON_DEFAULT_HEX: &'static str = FOREGROUND_HEX;
-pub const BUTTON_HOVER_HEX: &'static str = "2a3147";
-pub const BUTTON_PRESSED_HEX: &'static str = "202536";
+// Colours come from the ui theme, see theme::UiTheme
 
 pub struct TextStyle {
     pub size: f32,
-    pub color_hex: &'static str,
+    pub color: ThemeColor,
     pub font: &'static str,
     pub allignment: TextAlignment,
 }
@@ -24,26 +17,38 @@ pub struct ButtonStyle {
     pub width: Val,
     pub height: Val,
 
-    pub color_hex: &'static str,
-
     pub text_style: Option<TextStyle>,
 }
 
 
 pub const TITLE_TEXT_STYLE: TextStyle = TextStyle {
     size: 96.0,
-    color_hex: TEXT_BOLD_HEX,
+    color: ThemeColor::TextBold,
     font: "fonts/Wonkies.ttf",
     allignment: TextAlignment::Center,
 };
 
 pub const BODY_TEXT_STYLE: TextStyle = TextStyle {
     size: 48.0,
-    color_hex: TEXT_HEX,
+    color: ThemeColor::Text,
     font: "fonts/Wonkies.ttf",
     allignment: TextAlignment::Center,
 };
 
+pub const SMALL_TEXT_STYLE: TextStyle = TextStyle {
+    size: 32.0,
+    color: ThemeColor::Text,
+    font: "fonts/Wonkies.ttf",
+    allignment: TextAlignment::Center,
+};
+
+pub const DEBUG_TEXT_STYLE: TextStyle = TextStyle {
+    size: 24.0,
+    color: ThemeColor::Text,
+    font: "fonts/Wonkies.ttf",
+    allignment: TextAlignment::Left,
+};
+
 // Ui Button style
 pub const BUTTON_STYLE: ButtonStyle = ButtonStyle {
     justify_content: JustifyContent::Center,
@@ -52,9 +57,17 @@ pub const BUTTON_STYLE: ButtonStyle = ButtonStyle {
     width: Val::Percent(30.0),
     height: Val::Percent(8.0),
 
-    color_hex: BUTTON_DEFAULT_HEX,
-
     text_style: Some(BODY_TEXT_STYLE),
 };
 
+// Smaller button used for lists, such as the bindings on the controls screen
+// Fills the height of its row
+pub const LIST_BUTTON_STYLE: ButtonStyle = ButtonStyle {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+
+    width: Val::Percent(45.0),
+    height: Val::Percent(100.0),
 
+    text_style: Some(SMALL_TEXT_STYLE),
+};
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::{data, settings};
+
+const UI_THEMES_PATH: &str = "data/ui_themes.ron";
+
+// Palettes the ui can be drawn with, picked in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum UiPalette {
+    #[default]
+    Default,
+    HighContrast,
+    ColourBlind,
+}
+
+// Part of the ui a colour is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeColor {
+    Background, // Main menu background
+    Overlay, // Background of menus drawn over the game
+    Foreground, // Panels, such as the game over summary
+    Text,
+    TextBold, // Titles and highlights
+    ButtonDefault,
+    ButtonHover,
+    ButtonPressed,
+}
+
+// Hex colours of a palette, as written in UI_THEMES_PATH
+#[derive(Deserialize, Clone, Debug)]
+struct PaletteColors {
+    background: String,
+    overlay: String,
+    foreground: String,
+    text: String,
+    text_bold: String,
+    button_default: String,
+    button_hover: String,
+    button_pressed: String,
+}
+
+// Colours the ui is currently drawn with
+#[derive(Resource, Clone, Debug)]
+pub struct UiTheme {
+    pub palette: UiPalette,
+
+    background: Color,
+    overlay: Color,
+    foreground: Color,
+    text: Color,
+    text_bold: Color,
+    button_default: Color,
+    button_hover: Color,
+    button_pressed: Color,
+}
+
+// Text coloured by the ui theme
+#[derive(Component)]
+pub struct ThemedText(pub ThemeColor);
+
+// Node background coloured by the ui theme
+#[derive(Component)]
+pub struct ThemedBackground(pub ThemeColor);
+
+pub struct UiThemePlugin;
+
+impl Plugin for UiThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, change_ui_theme.run_if(resource_changed::<settings::Settings>()))
+            .add_systems(PostUpdate, (theme_texts, theme_backgrounds, theme_buttons));
+    }
+}
+
+impl UiPalette {
+    pub fn next(&self) -> Self {
+        match self {
+            UiPalette::Default => UiPalette::HighContrast,
+            UiPalette::HighContrast => UiPalette::ColourBlind,
+            UiPalette::ColourBlind => UiPalette::Default,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            UiPalette::Default => "Default",
+            UiPalette::HighContrast => "High contrast",
+            UiPalette::ColourBlind => "Colour-blind",
+        }
+    }
+}
+
+impl UiTheme {
+    // Load a palette from UI_THEMES_PATH, or fall back to the default colours when it can't be loaded
+    pub fn load(palette: UiPalette) -> Self {
+        let colors = match data::load_ron::<HashMap<UiPalette, PaletteColors>>(UI_THEMES_PATH) {
+            Ok(mut palettes) => palettes.remove(&palette),
+            Err(error) => {
+                warn!("{}, using the default ui colours", error);
+                None
+            },
+        };
+
+        match colors {
+            Some(colors) => UiTheme::from_colors(palette, &colors),
+            None => UiTheme {palette, ..UiTheme::default()},
+        }
+    }
+
+    fn from_colors(palette: UiPalette, colors: &PaletteColors) -> Self {
+        let default = UiTheme::default();
+
+        UiTheme {
+            palette,
+            background: theme_color(&colors.background, default.background),
+            overlay: theme_color(&colors.overlay, default.overlay),
+            foreground: theme_color(&colors.foreground, default.foreground),
+            text: theme_color(&colors.text, default.text),
+            text_bold: theme_color(&colors.text_bold, default.text_bold),
+            button_default: theme_color(&colors.button_default, default.button_default),
+            button_hover: theme_color(&colors.button_hover, default.button_hover),
+            button_pressed: theme_color(&colors.button_pressed, default.button_pressed),
+        }
+    }
+
+    pub fn color(&self, theme_color: ThemeColor) -> Color {
+        match theme_color {
+            ThemeColor::Background => self.background,
+            ThemeColor::Overlay => self.overlay,
+            ThemeColor::Foreground => self.foreground,
+            ThemeColor::Text => self.text,
+            ThemeColor::TextBold => self.text_bold,
+            ThemeColor::ButtonDefault => self.button_default,
+            ThemeColor::ButtonHover => self.button_hover,
+            ThemeColor::ButtonPressed => self.button_pressed,
+        }
+    }
+
+    // Colour of a button in an interaction state
+    pub fn button_color(&self, interaction: Interaction) -> Color {
+        match interaction {
+            Interaction::Pressed => self.button_pressed,
+            Interaction::Hovered => self.button_hover,
+            Interaction::None => self.button_default,
+        }
+    }
+}
+
+// Color scheme using https://lospec.com/palette-list/sweetie-16 color palette
+impl Default for UiTheme {
+    fn default() -> Self {
+        UiTheme {
+            palette: UiPalette::Default,
+            background: Color::rgb_u8(0x1a, 0x1c, 0x2c),
+            overlay: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            foreground: Color::rgb_u8(0x33, 0x3c, 0x57),
+            text: Color::rgb_u8(0xf4, 0xf4, 0xf4),
+            text_bold: Color::rgb_u8(0x94, 0xb0, 0xc2),
+            button_default: Color::rgb_u8(0x33, 0x3c, 0x57),
+            button_hover: Color::rgb_u8(0x2a, 0x31, 0x47),
+            button_pressed: Color::rgb_u8(0x20, 0x25, 0x36),
+        }
+    }
+}
+
+// Parse a palette colour, invalid colours keep the default colour
+fn theme_color(hex: &str, default: Color) -> Color {
+    Color::hex(hex).unwrap_or_else(|_| {
+        warn!("Invalid ui palette colour {}", hex);
+        default
+    })
+}
+
+fn change_ui_theme(settings: Res<settings::Settings>, mut theme: ResMut<UiTheme>) {
+    if settings.ui_palette != theme.palette {
+        *theme = UiTheme::load(settings.ui_palette);
+    }
+}
+
+// The theme systems colour newly spawned ui, and recolour all of it when the theme changes
+fn theme_texts(theme: Res<UiTheme>, mut text_query: Query<(Ref<ThemedText>, &mut Text)>) {
+    for (themed_text, mut text) in text_query.iter_mut() {
+        if !theme.is_changed() && !themed_text.is_added() {
+            continue;
+        }
+
+        let color = theme.color(themed_text.0);
+        for section in text.sections.iter_mut() {
+            // Keep the transparency of text which fades, such as the level up banner
+            section.style.color = if themed_text.is_added() {
+                color
+            } else {
+                color.with_a(section.style.color.a())
+            };
+        }
+    }
+}
+
+fn theme_backgrounds(theme: Res<UiTheme>, mut background_query: Query<(Ref<ThemedBackground>, &mut BackgroundColor)>) {
+    for (themed_background, mut background_color) in background_query.iter_mut() {
+        if theme.is_changed() || themed_background.is_added() {
+            background_color.0 = theme.color(themed_background.0);
+        }
+    }
+}
+
+fn theme_buttons(theme: Res<UiTheme>, mut button_query: Query<(Ref<Interaction>, &mut BackgroundColor), With<Button>>) {
+    for (interaction, mut background_color) in button_query.iter_mut() {
+        if theme.is_changed() || interaction.is_added() {
+            background_color.0 = theme.button_color(*interaction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_palette_loads() {
+        let palettes = data::load_ron::<HashMap<UiPalette, PaletteColors>>(UI_THEMES_PATH).unwrap();
+
+        for palette in [UiPalette::Default, UiPalette::HighContrast, UiPalette::ColourBlind] {
+            let colors = palettes.get(&palette).unwrap();
+            for hex in [&colors.background, &colors.overlay, &colors.foreground, &colors.text, &colors.text_bold, &colors.button_default, &colors.button_hover, &colors.button_pressed] {
+                assert!(Color::hex(hex).is_ok(), "{:?} has an invalid colour {}", palette, hex);
+            }
+        }
+    }
+}